@@ -0,0 +1,80 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use paxhtml::{builder::Builder, bumpalo::Bump, Arena, Document};
+
+fn inline_document(c: &mut Criterion) {
+    let bump = Bump::new();
+    let b = Builder::new(&bump);
+    let doc = b.document([b.p([])(b.fragment((0..5000).map(|i| {
+        if i % 2 == 0 {
+            b.span([b.attr(("class", "word"))])(b.text("word"))
+        } else {
+            b.text(" ")
+        }
+    })))]);
+
+    c.bench_function("render 5k inline elements", |bench| {
+        bench.iter(|| doc.write_to_string().unwrap())
+    });
+}
+
+fn build_nested_document(b: Builder<'_>) -> Document<'_> {
+    b.document([b.body([])(b.fragment((0..100).map(|i| {
+        b.section([b.attr(("id", format!("section-{i}")))])([
+            b.h2([])(b.text("Section")),
+            b.ul([])(b.fragment((0..10).map(|_| {
+                b.li([])(b.div([])([
+                    b.p([])([b.strong([])(b.text("Item")), b.text(" description")]),
+                    b.a([b.attr(("href", "#"))])(b.text("link")),
+                ]))
+            }))),
+        ])
+    })))])
+}
+
+fn nested_document(c: &mut Criterion) {
+    let bump = Bump::new();
+    let doc = build_nested_document(Builder::new(&bump));
+
+    c.bench_function("render nested document", |bench| {
+        bench.iter(|| doc.write_to_string().unwrap())
+    });
+    c.bench_function("render nested document (io::Write)", |bench| {
+        bench.iter(|| {
+            let mut output = vec![];
+            doc.write(&mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        })
+    });
+    c.bench_function("render nested document (fmt::Write)", |bench| {
+        bench.iter(|| {
+            let mut output = String::new();
+            doc.write_fmt(&mut output).unwrap();
+            output
+        })
+    });
+}
+
+fn build_and_render(c: &mut Criterion) {
+    c.bench_function("build and render nested document (new Bump)", |bench| {
+        bench.iter(|| {
+            let bump = Bump::new();
+            let html = build_nested_document(Builder::new(&bump))
+                .write_to_string()
+                .unwrap();
+            html
+        })
+    });
+    let mut arena = Arena::new();
+    c.bench_function("build and render nested document (reused Arena)", |bench| {
+        bench.iter(|| {
+            arena.scope(|bump| {
+                build_nested_document(Builder::new(bump))
+                    .write_to_string()
+                    .unwrap()
+            })
+        })
+    });
+}
+
+criterion_group!(benches, inline_document, nested_document, build_and_render);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+use bumpalo::Bump;
+
+/// A reusable bump allocator for rendering many documents, such as one per request in a
+/// server.
+///
+/// Each call to [`Arena::scope`] hands out the underlying [`Bump`], and resets it once the
+/// scope ends. Resetting keeps the allocator's largest chunk of memory around, so after the
+/// first few scopes, rendering no longer needs to allocate new chunks.
+///
+/// Elements and documents cannot outlive the scope that created them: the scope's result
+/// cannot borrow from the bump, so it is typically the rendered `String`.
+///
+/// ```
+/// use paxhtml::{builder::Builder, Arena};
+///
+/// let mut arena = Arena::new();
+/// for name in ["Alice", "Bob"] {
+///     let html = arena.scope(|bump| {
+///         let b = Builder::new(bump);
+///         b.document([b.p([])(b.text(name))]).write_to_string().unwrap()
+///     });
+///     assert_eq!(html, format!("<p>{name}</p>"));
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Arena {
+    bump: Bump,
+}
+impl Arena {
+    /// Create a new, empty arena.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` with the arena's bump allocator, then reset the allocator, freeing everything
+    /// allocated within the scope.
+    pub fn scope<R>(&mut self, f: impl for<'bump> FnOnce(&'bump Bump) -> R) -> R {
+        let result = f(&self.bump);
+        self.bump.reset();
+        result
+    }
+
+    /// The number of bytes currently held by the arena's allocator, including memory that
+    /// has been reset and is available for reuse.
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+
+    #[test]
+    fn reuses_memory_across_scopes() {
+        let mut arena = Arena::new();
+        let render = |arena: &mut Arena, i: usize| {
+            arena.scope(|bump| {
+                let b = Builder::new(bump);
+                b.document([b.ul([])(
+                    b.fragment((0..100).map(|j| b.li([])(b.text(&format!("{i}-{j}"))))),
+                )])
+                .write_compact_to_string()
+                .unwrap()
+            })
+        };
+
+        // Let the allocator grow to its steady-state size
+        for i in 0..10 {
+            render(&mut arena, i);
+        }
+        let steady_state = arena.allocated_bytes();
+
+        for i in 0..1000 {
+            let html = render(&mut arena, i);
+            assert!(html.starts_with(&format!("<ul><li>{i}-0</li>")));
+        }
+        assert!(arena.allocated_bytes() <= steady_state);
+    }
+}
@@ -1,9 +1,20 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+};
+
 use bumpalo::collections::String as BumpString;
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 
 /// Represents a typed attribute value.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Floats are compared and hashed by their bit patterns, so that values compare equal if
+/// and only if they render identically (e.g. `0.0` and `-0.0` differ, and `NaN` equals
+/// itself). This allows attributes, and the elements containing them, to implement [`Eq`]
+/// and [`Hash`](std::hash::Hash).
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum AttributeValue<'bump> {
@@ -17,6 +28,30 @@ pub enum AttributeValue<'bump> {
     Bool(bool),
 }
 
+impl PartialEq for AttributeValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+impl Eq for AttributeValue<'_> {}
+impl core::hash::Hash for AttributeValue<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::String(s) => s.hash(state),
+            Self::Int(i) => i.hash(state),
+            Self::Float(f) => f.to_bits().hash(state),
+            Self::Bool(b) => b.hash(state),
+        }
+    }
+}
+
 impl<'bump> AttributeValue<'bump> {
     /// Returns the value as a string slice if this is a String variant.
     pub fn as_str(&self) -> Option<&str> {
@@ -67,7 +102,7 @@ impl<'bump> AttributeValue<'bump> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// A key-value pair for an HTML attribute.
 pub struct Attribute<'bump> {
@@ -126,6 +161,62 @@ impl<'bump> Attribute<'bump> {
         }
     }
 
+    /// Create a `data-*` attribute with the given suffix and value.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `suffix` is empty, contains ASCII uppercase letters, or contains
+    /// characters that are not permitted in attribute names.
+    pub fn data(
+        bump: &'bump Bump,
+        suffix: &str,
+        value: &str,
+    ) -> Result<Self, InvalidAttributeName> {
+        Self::prefixed(bump, "data-", suffix, value)
+    }
+
+    /// Create an `aria-*` attribute with the given suffix and value.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `suffix` is empty, contains ASCII uppercase letters, or contains
+    /// characters that are not permitted in attribute names.
+    pub fn aria(
+        bump: &'bump Bump,
+        suffix: &str,
+        value: &str,
+    ) -> Result<Self, InvalidAttributeName> {
+        Self::prefixed(bump, "aria-", suffix, value)
+    }
+
+    fn prefixed(
+        bump: &'bump Bump,
+        prefix: &str,
+        suffix: &str,
+        value: &str,
+    ) -> Result<Self, InvalidAttributeName> {
+        let is_valid = !suffix.is_empty()
+            && suffix.chars().all(|c| {
+                !c.is_ascii_uppercase()
+                    && !c.is_whitespace()
+                    && !c.is_control()
+                    && !matches!(c, '"' | '\'' | '>' | '/' | '=')
+            });
+        if !is_valid {
+            return Err(InvalidAttributeName {
+                name: format!("{prefix}{suffix}"),
+            });
+        }
+
+        let mut key = BumpString::with_capacity_in(prefix.len() + suffix.len(), bump);
+        key.push_str(prefix);
+        key.push_str(suffix);
+        Ok(Attribute {
+            key,
+            value: Some(AttributeValue::String(BumpString::from_str_in(value, bump))),
+        })
+    }
+
     /// Get the value as a string slice, if present and is a String variant.
     ///
     /// For non-string values, this returns `None`. Use `value_to_str()` to get
@@ -134,14 +225,205 @@ impl<'bump> Attribute<'bump> {
         self.value.as_ref().and_then(|v| v.as_str())
     }
 
+    /// Get the value as a string slice, or `default` if there is no String value.
+    ///
+    /// As with [`Self::value_as_str`], boolean attributes and non-string values use
+    /// `default`.
+    pub fn value_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.value_as_str().unwrap_or(default)
+    }
+
+    /// Returns `true` if this is a boolean attribute, i.e. it has no value (`disabled`).
+    ///
+    /// Note that an attribute with a [`AttributeValue::Bool`] value is not a boolean
+    /// attribute, as it is rendered with its value (`draggable="true"`).
+    pub fn is_boolean(&self) -> bool {
+        self.value.is_none()
+    }
+
     /// Get the string representation of the value for HTML output.
     ///
     /// This allocates a new string in the bump allocator for non-string variants.
     pub fn value_to_str(&self, bump: &'bump Bump) -> Option<BumpString<'bump>> {
         self.value.as_ref().map(|v| v.to_str(bump))
     }
+
+    /// The order used when sorting attributes: alphabetically by key, with boolean
+    /// attributes placed before valued attributes sharing the same key.
+    ///
+    /// Attributes that compare equal keep their relative order when sorted.
+    pub fn sort_order(&self, other: &Self) -> core::cmp::Ordering {
+        self.key
+            .as_str()
+            .cmp(other.key.as_str())
+            .then_with(|| self.value.is_some().cmp(&other.value.is_some()))
+    }
+
+    /// Copy this attribute into another bump allocator.
+    pub(crate) fn clone_in<'other>(&self, bump: &'other Bump) -> Attribute<'other> {
+        Attribute {
+            key: BumpString::from_str_in(&self.key, bump),
+            value: self.value.as_ref().map(|value| match value {
+                AttributeValue::String(s) => {
+                    AttributeValue::String(BumpString::from_str_in(s, bump))
+                }
+                AttributeValue::Int(i) => AttributeValue::Int(*i),
+                AttributeValue::Float(f) => AttributeValue::Float(*f),
+                AttributeValue::Bool(b) => AttributeValue::Bool(*b),
+            }),
+        }
+    }
+}
+
+impl<'bump> Attribute<'bump> {
+    /// Create an attribute from a value that implements [IntoAttributeValue], or `None` if
+    /// the value indicates that the attribute should be omitted.
+    ///
+    /// This is used by [`html!`](crate::html) for interpolated attribute values, such as
+    /// `disabled={is_disabled}`.
+    pub fn from_value(
+        bump: &'bump Bump,
+        key: &str,
+        value: impl IntoAttributeValue<'bump>,
+    ) -> Option<Self> {
+        value.into_attribute_value(bump).map(|value| Attribute {
+            key: BumpString::from_str_in(key, bump),
+            value,
+        })
+    }
 }
 
+/// Trait for types that can be used as the value of an attribute.
+///
+/// Conversions return `None` if the attribute should be omitted entirely, `Some(None)` for a
+/// boolean attribute without a value, and `Some(Some(value))` otherwise. `true` produces a
+/// boolean attribute and `false` omits it, matching how HTML interprets boolean attributes,
+/// and `None` omits it as well.
+///
+/// This is implemented for strings in their common owned and borrowed forms, `char`,
+/// numbers, and references to these, so values yielded by iterators can be used directly.
+/// Other types can be converted with `.to_string()`.
+pub trait IntoAttributeValue<'bump> {
+    /// Convert this value into an attribute value using the given bump allocator.
+    fn into_attribute_value(self, bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>>;
+}
+impl<'bump> IntoAttributeValue<'bump> for AttributeValue<'bump> {
+    fn into_attribute_value(self, _bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        Some(Some(self))
+    }
+}
+impl<'bump> IntoAttributeValue<'bump> for &str {
+    fn into_attribute_value(self, bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        Some(Some(AttributeValue::String(BumpString::from_str_in(
+            self, bump,
+        ))))
+    }
+}
+impl<'bump> IntoAttributeValue<'bump> for BumpString<'bump> {
+    fn into_attribute_value(self, _bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        Some(Some(AttributeValue::String(self)))
+    }
+}
+impl<'bump> IntoAttributeValue<'bump> for char {
+    fn into_attribute_value(self, bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        self.encode_utf8(&mut [0; 4]).into_attribute_value(bump)
+    }
+}
+macro_rules! str_attribute_values {
+    ($($ty:ty),*) => {
+        $(
+            impl<'bump> IntoAttributeValue<'bump> for $ty {
+                fn into_attribute_value(
+                    self,
+                    bump: &'bump Bump,
+                ) -> Option<Option<AttributeValue<'bump>>> {
+                    let value: &str = &self;
+                    value.into_attribute_value(bump)
+                }
+            }
+        )*
+    };
+}
+str_attribute_values!(
+    &&str,
+    String,
+    &String,
+    alloc::borrow::Cow<'_, str>,
+    &alloc::borrow::Cow<'_, str>,
+    &BumpString<'_>,
+    Box<str>,
+    alloc::rc::Rc<str>,
+    alloc::sync::Arc<str>
+);
+impl<'bump> IntoAttributeValue<'bump> for bool {
+    fn into_attribute_value(self, _bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        self.then_some(None)
+    }
+}
+impl<'bump> IntoAttributeValue<'bump> for f32 {
+    fn into_attribute_value(self, _bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        Some(Some(AttributeValue::Float(self.into())))
+    }
+}
+impl<'bump> IntoAttributeValue<'bump> for f64 {
+    fn into_attribute_value(self, _bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        Some(Some(AttributeValue::Float(self)))
+    }
+}
+/// `None` omits the attribute, while `Some` is converted as its contained value.
+impl<'bump, T: IntoAttributeValue<'bump>> IntoAttributeValue<'bump> for Option<T> {
+    fn into_attribute_value(self, bump: &'bump Bump) -> Option<Option<AttributeValue<'bump>>> {
+        self.and_then(|value| value.into_attribute_value(bump))
+    }
+}
+macro_rules! int_attribute_values {
+    ($($ty:ty),*) => {
+        $(
+            impl<'bump> IntoAttributeValue<'bump> for $ty {
+                fn into_attribute_value(
+                    self,
+                    _bump: &'bump Bump,
+                ) -> Option<Option<AttributeValue<'bump>>> {
+                    Some(Some(AttributeValue::Int(self as i128)))
+                }
+            }
+        )*
+    };
+}
+int_attribute_values!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+// References to scalars, such as those yielded when iterating over a slice, are converted
+// as the value they point to
+macro_rules! ref_attribute_values {
+    ($($ty:ty),*) => {
+        $(
+            impl<'bump> IntoAttributeValue<'bump> for &$ty {
+                fn into_attribute_value(
+                    self,
+                    bump: &'bump Bump,
+                ) -> Option<Option<AttributeValue<'bump>>> {
+                    (*self).into_attribute_value(bump)
+                }
+            }
+        )*
+    };
+}
+ref_attribute_values!(
+    bool, char, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize
+);
+
+/// Error returned when constructing an attribute with an invalid name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidAttributeName {
+    /// The full name of the attribute that was rejected.
+    pub name: String,
+}
+impl core::fmt::Display for InvalidAttributeName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid attribute name '{}'", self.name)
+    }
+}
+impl core::error::Error for InvalidAttributeName {}
+
 /// Trait for types that can be converted into an Attribute with a bump allocator.
 pub trait IntoAttribute<'bump> {
     /// Convert this value into an Attribute using the given bump allocator.
@@ -162,6 +444,14 @@ impl<'bump> IntoAttribute<'bump> for (&str, &str) {
         Attribute::new(bump, self.0, self.1)
     }
 }
+impl<'bump> IntoAttribute<'bump> for (&str, Option<&str>) {
+    fn into_attribute(self, bump: &'bump Bump) -> Attribute<'bump> {
+        match self.1 {
+            Some(value) => Attribute::new(bump, self.0, value),
+            None => Attribute::boolean(bump, self.0),
+        }
+    }
+}
 impl<'bump> IntoAttribute<'bump> for (&str, String) {
     fn into_attribute(self, bump: &'bump Bump) -> Attribute<'bump> {
         Attribute::new(bump, self.0, &self.1)
@@ -185,12 +475,12 @@ pub enum AttributeParseError {
     ///
     /// Contains:
     /// - The quote character that was not closed
-    /// - The position of the unclosed quote
+    /// - The byte offset of the unclosed quote
     /// - The partial attribute value that was parsed
     UnclosedQuote {
         /// The quote character that was not closed (either ' or ")
         quote: char,
-        /// The position of the unclosed quote in the input string
+        /// The byte offset of the unclosed quote in the input string
         position: usize,
         /// The partial attribute value that was parsed
         partial_value: String,
@@ -199,12 +489,12 @@ pub enum AttributeParseError {
     ///
     /// Contains:
     /// - The unexpected character that caused the error
-    /// - The position where the error occurred
+    /// - The byte offset where the error occurred
     /// - The context of what was being parsed
     InvalidSyntax {
         /// The unexpected character that caused the error
         unexpected: char,
-        /// The position where the error occurred
+        /// The byte offset in the input string where the error occurred
         position: usize,
         /// What was being parsed when the error occurred
         context: ParseContext,
@@ -221,8 +511,8 @@ pub enum ParseContext {
     /// Expected a quote or valid unquoted value character but found something else
     ExpectedQuoteOrValue,
 }
-impl std::fmt::Display for AttributeParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for AttributeParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             AttributeParseError::UnclosedQuote {
                 quote,
@@ -255,7 +545,7 @@ impl std::fmt::Display for AttributeParseError {
         }
     }
 }
-impl std::error::Error for AttributeParseError {}
+impl core::error::Error for AttributeParseError {}
 
 impl<'bump> Attribute<'bump> {
     /// Parse a string of attributes into a vector of attributes.
@@ -276,7 +566,7 @@ impl<'bump> Attribute<'bump> {
     ///
     /// Returns an error if the string does not respect the HTML attribute syntax.
     /// The error will contain detailed information about what caused the parsing failure,
-    /// including the position of the error and the context of what was being parsed.
+    /// including the byte offset of the error and the context of what was being parsed.
     pub fn parse_from_str(
         bump: &'bump Bump,
         s: &str,
@@ -292,7 +582,7 @@ impl<'bump> Attribute<'bump> {
         }
 
         let mut attributes = BumpVec::new_in(bump);
-        let mut chars = s.chars().enumerate().peekable();
+        let mut chars = s.char_indices().peekable();
         let mut current_key = String::new();
         let mut current_value: Option<String> = None;
         let mut in_quotes = false;
@@ -304,7 +594,7 @@ impl<'bump> Attribute<'bump> {
             match state {
                 ParseState::BeforeAttribute => {
                     match c {
-                        ' ' | '\t' | '\n' => continue, // Skip whitespace between attributes
+                        c if c.is_whitespace() => continue, // Skip whitespace between attributes
                         '=' => {
                             return Err(AttributeParseError::InvalidSyntax {
                                 unexpected: c,
@@ -320,7 +610,7 @@ impl<'bump> Attribute<'bump> {
                 }
                 ParseState::InName => {
                     match c {
-                        ' ' | '\t' | '\n' => {
+                        c if c.is_whitespace() => {
                             // Look ahead to see if there's an equals sign
                             let temp_iter = chars.clone();
                             let mut found_equals = false;
@@ -351,7 +641,7 @@ impl<'bump> Attribute<'bump> {
                 }
                 ParseState::BeforeEquals => {
                     match c {
-                        ' ' | '\t' | '\n' => continue, // Skip whitespace before equals
+                        c if c.is_whitespace() => continue, // Skip whitespace before equals
                         '=' => {
                             state = ParseState::AfterEquals;
                             current_value = Some(String::new());
@@ -367,7 +657,7 @@ impl<'bump> Attribute<'bump> {
                 }
                 ParseState::AfterEquals => {
                     match c {
-                        ' ' | '\t' | '\n' => continue, // Skip whitespace after equals
+                        c if c.is_whitespace() => continue, // Skip whitespace after equals
                         '"' | '\'' => {
                             quote_char = Some(c);
                             quote_start_pos = pos;
@@ -376,7 +666,7 @@ impl<'bump> Attribute<'bump> {
                         }
                         _ => {
                             if let Some(ref mut value) = current_value {
-                                if !c.is_alphanumeric() && c != '-' && c != '_' {
+                                if !is_unquoted_value_char(c) {
                                     return Err(AttributeParseError::InvalidSyntax {
                                         unexpected: c,
                                         position: pos,
@@ -405,7 +695,7 @@ impl<'bump> Attribute<'bump> {
                     }
                 }
                 ParseState::InUnquotedValue => match c {
-                    ' ' | '\t' | '\n' => {
+                    c if c.is_whitespace() => {
                         attributes.push(Attribute::with_optional_value(
                             bump,
                             &current_key,
@@ -416,7 +706,7 @@ impl<'bump> Attribute<'bump> {
                         state = ParseState::BeforeAttribute;
                     }
                     _ => {
-                        if !c.is_alphanumeric() && c != '-' && c != '_' {
+                        if !is_unquoted_value_char(c) {
                             return Err(AttributeParseError::InvalidSyntax {
                                 unexpected: c,
                                 position: pos,
@@ -451,6 +741,11 @@ impl<'bump> Attribute<'bump> {
         Ok(attributes)
     }
 }
+// Characters permitted in an unquoted attribute value, as per the HTML spec
+fn is_unquoted_value_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '"' | '\'' | '=' | '<' | '>' | '`')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -595,14 +890,14 @@ mod tests {
     #[test]
     fn test_parse_error_invalid_unquoted_value() {
         let bump = Bump::new();
-        let result = Attribute::parse_from_str(&bump, "id=test!");
+        let result = Attribute::parse_from_str(&bump, "id=test\"");
         match result {
             Err(AttributeParseError::InvalidSyntax {
                 unexpected,
                 position,
                 context,
             }) => {
-                assert_eq!(unexpected, '!');
+                assert_eq!(unexpected, '"');
                 assert_eq!(position, 7);
                 assert_eq!(context, ParseContext::ExpectedQuoteOrValue);
             }
@@ -610,6 +905,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_unquoted_values_with_urls() {
+        let bump = Bump::new();
+        let attributes =
+            Attribute::parse_from_str(&bump, "href=/a/b.html?x&y#top data-x=a.b.c").unwrap();
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(attributes[0].key.as_str(), "href");
+        assert_eq!(attributes[0].value_as_str(), Some("/a/b.html?x&y#top"));
+        assert_eq!(attributes[1].key.as_str(), "data-x");
+        assert_eq!(attributes[1].value_as_str(), Some("a.b.c"));
+
+        assert!(matches!(
+            Attribute::parse_from_str(&bump, "href=<a"),
+            Err(AttributeParseError::InvalidSyntax {
+                unexpected: '<',
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_position_is_byte_offset() {
+        let bump = Bump::new();
+        let input = "title=\"café\" =x";
+        let result = Attribute::parse_from_str(&bump, input);
+        match result {
+            Err(AttributeParseError::InvalidSyntax {
+                unexpected,
+                position,
+                ..
+            }) => {
+                assert_eq!(unexpected, '=');
+                assert_eq!(position, 14);
+                assert_eq!(&input[position..], "=x");
+            }
+            _ => panic!("Expected InvalidSyntax error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_crlf_whitespace() {
+        let bump = Bump::new();
+        let attributes = Attribute::parse_from_str(
+            &bump,
+            "id=\"test\"\r\nwidth\r\n=\r\n150\r\n\x0cdisabled\r\n",
+        )
+        .unwrap();
+        assert_eq!(attributes.len(), 3);
+        assert_eq!(attributes[0].key.as_str(), "id");
+        assert_eq!(attributes[0].value_as_str(), Some("test"));
+        assert_eq!(attributes[1].key.as_str(), "width");
+        assert_eq!(attributes[1].value_as_str(), Some("150"));
+        assert_eq!(attributes[2].key.as_str(), "disabled");
+        assert_eq!(attributes[2].value, None);
+    }
+
     #[test]
     fn test_parse_space_after_key() {
         let bump = Bump::new();
@@ -620,4 +971,45 @@ mod tests {
         assert_eq!(attributes[1].key.as_str(), "height");
         assert_eq!(attributes[1].value_as_str(), Some("80"));
     }
+
+    #[test]
+    fn test_data_and_aria_attributes() {
+        let bump = Bump::new();
+        let data = Attribute::data(&bump, "foo", "bar").unwrap();
+        assert_eq!(data.key.as_str(), "data-foo");
+        assert_eq!(data.value_as_str(), Some("bar"));
+
+        let aria = Attribute::aria(&bump, "label", "Close").unwrap();
+        assert_eq!(aria.key.as_str(), "aria-label");
+        assert_eq!(aria.value_as_str(), Some("Close"));
+    }
+
+    #[test]
+    fn test_data_attribute_rejects_invalid_suffix() {
+        let bump = Bump::new();
+        assert_eq!(
+            Attribute::data(&bump, "foo bar", "x"),
+            Err(InvalidAttributeName {
+                name: "data-foo bar".to_string()
+            })
+        );
+        assert!(Attribute::data(&bump, "fooBar", "x").is_err());
+        assert!(Attribute::aria(&bump, "", "x").is_err());
+    }
+
+    #[test]
+    fn value_helpers() {
+        let bump = Bump::new();
+        let valued = Attribute::new(&bump, "href", "/about");
+        assert_eq!(valued.value_or("#"), "/about");
+        assert!(!valued.is_boolean());
+
+        let boolean = Attribute::boolean(&bump, "disabled");
+        assert!(boolean.is_boolean());
+        assert_eq!(boolean.value_or("fallback"), "fallback");
+
+        let typed = Attribute::new_bool(&bump, "draggable", true);
+        assert!(!typed.is_boolean());
+        assert_eq!(typed.value_or("fallback"), "fallback");
+    }
 }
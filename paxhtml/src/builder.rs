@@ -41,6 +41,101 @@ impl<'bump> Builder<'bump> {
         value.into_attribute(self.bump)
     }
 
+    /// Create a list of attributes from a slice of values that implement [IntoAttribute],
+    /// preserving their order.
+    ///
+    /// Pairs of `(&str, Option<&str>)` can be used to mix valued and boolean attributes.
+    ///
+    /// ```
+    /// use paxhtml::{bumpalo::Bump, builder::Builder};
+    ///
+    /// let bump = Bump::new();
+    /// let b = Builder::new(&bump);
+    /// let input = b.input(b.attr_list(&[("type", Some("checkbox")), ("checked", None)]));
+    /// ```
+    pub fn attr_list<A: IntoAttribute<'bump> + Copy>(
+        &self,
+        values: &[A],
+    ) -> BumpVec<'bump, Attribute<'bump>> {
+        BumpVec::from_iter_in(
+            values.iter().map(|v| v.into_attribute(self.bump)),
+            self.bump,
+        )
+    }
+
+    /// Create a list of attributes from optional attributes, dropping any that are `None`.
+    ///
+    /// ```
+    /// use paxhtml::{bumpalo::Bump, builder::Builder};
+    ///
+    /// let bump = Bump::new();
+    /// let b = Builder::new(&bump);
+    /// let id: Option<&str> = None;
+    /// let div = b.div(b.optional_attrs([
+    ///     Some(b.attr(("class", "card"))),
+    ///     id.map(|id| b.attr(("id", id))),
+    /// ]))([]);
+    /// assert_eq!(div.to_string(), r#"<div class="card"></div>"#);
+    /// ```
+    pub fn optional_attrs(
+        &self,
+        attributes: impl IntoIterator<Item = Option<Attribute<'bump>>>,
+    ) -> BumpVec<'bump, Attribute<'bump>> {
+        BumpVec::from_iter_in(attributes.into_iter().flatten(), self.bump)
+    }
+
+    /// Create a `class` attribute from a list of class names, separated by spaces.
+    ///
+    /// Empty class names are skipped.
+    pub fn classes(&self, classes: &[&str]) -> Attribute<'bump> {
+        self.class_attr(classes.iter().copied())
+    }
+
+    /// Create a `class` attribute containing each class name whose condition is `true`,
+    /// separated by spaces.
+    ///
+    /// If no class is enabled, the attribute is still produced with an empty value, so
+    /// that the attribute list has the same shape regardless of the conditions.
+    pub fn classes_if(&self, classes: &[(&str, bool)]) -> Attribute<'bump> {
+        self.class_attr(
+            classes
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(class, _)| *class),
+        )
+    }
+
+    /// Create a `style` attribute from a list of `(property, value)` pairs, formatted as
+    /// `property: value` and separated by `; `.
+    ///
+    /// Pairs with an empty property or value are skipped.
+    pub fn style(&self, declarations: &[(&str, &str)]) -> Attribute<'bump> {
+        let mut value = BumpString::new_in(self.bump);
+        for (property, property_value) in declarations {
+            if property.is_empty() || property_value.is_empty() {
+                continue;
+            }
+            if !value.is_empty() {
+                value.push_str("; ");
+            }
+            value.push_str(property);
+            value.push_str(": ");
+            value.push_str(property_value);
+        }
+        Attribute::new(self.bump, "style", &value)
+    }
+
+    fn class_attr<'a>(&self, classes: impl IntoIterator<Item = &'a str>) -> Attribute<'bump> {
+        let mut value = BumpString::new_in(self.bump);
+        for class in classes.into_iter().filter(|c| !c.is_empty()) {
+            if !value.is_empty() {
+                value.push(' ');
+            }
+            value.push_str(class);
+        }
+        Attribute::new(self.bump, "class", &value)
+    }
+
     /// Create a text element from a string.
     pub fn text(&self, text: &str) -> Element<'bump> {
         Element::Text {
@@ -55,40 +150,93 @@ impl<'bump> Builder<'bump> {
         }
     }
 
+    /// Create a comment (`<!--content-->`) containing `content` verbatim, without escaping.
+    ///
+    /// This is intended for conditional comments, such as `<!--[if mso]>...<![endif]-->`
+    /// in HTML emails, which must be written byte-for-byte. As with [`Self::raw`], the
+    /// content must be trusted: a `-->` within it ends the comment early, and anything
+    /// after it is emitted as HTML.
+    pub fn raw_comment(&self, content: &str) -> Element<'bump> {
+        let mut html = BumpString::with_capacity_in(content.len() + 7, self.bump);
+        html.push_str("<!--");
+        html.push_str(content);
+        html.push_str("-->");
+        Element::Raw { html }
+    }
+
+    /// Create a fragment of raw HTML elements, one for each of the given strings.
+    ///
+    /// Each string becomes a distinct child, so that pre-rendered fragments are laid out
+    /// as separate siblings when rendering.
+    pub fn raw_fragments<'a>(&self, htmls: impl IntoIterator<Item = &'a str>) -> Element<'bump> {
+        self.fragment(htmls.into_iter().map(|html| self.raw(html)))
+    }
+
     /// Create a tag element from a name, attributes, and a boolean indicating whether the tag is a void
     /// element (i.e. doesn't have a closing tag).
     ///
+    /// Tags for which [`is_void_tag`] returns `true` are always void, regardless of `void`.
+    ///
     /// The children are passed in as a single argument to the returned function.
     pub fn tag<E: IntoElement<'bump>>(
         &self,
         name: &str,
         attributes: impl IntoIterator<Item = Attribute<'bump>>,
         void: bool,
+    ) -> impl FnOnce(E) -> Element<'bump> {
+        self.tag_with_key(name, None, attributes, void)
+    }
+
+    /// Create a tag element as with [`Self::tag`], with a [key](Element::key) that identifies
+    /// it across renders.
+    ///
+    /// The key is not rendered; it is metadata for tools that diff element trees.
+    pub fn tag_keyed<E: IntoElement<'bump>>(
+        &self,
+        name: &str,
+        key: &str,
+        attributes: impl IntoIterator<Item = Attribute<'bump>>,
+        void: bool,
+    ) -> impl FnOnce(E) -> Element<'bump> {
+        self.tag_with_key(name, Some(key), attributes, void)
+    }
+
+    fn tag_with_key<E: IntoElement<'bump>>(
+        &self,
+        name: &str,
+        key: Option<&str>,
+        attributes: impl IntoIterator<Item = Attribute<'bump>>,
+        void: bool,
     ) -> impl FnOnce(E) -> Element<'bump> {
         let bump = self.bump;
         let name = BumpString::from_str_in(name, bump);
+        let key = key.map(|key| BumpString::from_str_in(key, bump));
         let attributes: BumpVec<'bump, Attribute<'bump>> = BumpVec::from_iter_in(attributes, bump);
 
-        move |children: E| {
-            let children = children.into_element(bump);
-            let children = match children {
-                Element::Fragment { children } => children,
-                Element::Empty => BumpVec::new_in(bump),
-                other => {
-                    let mut v = BumpVec::with_capacity_in(1, bump);
-                    v.push(other);
-                    v
-                }
-            };
-            Element::Tag {
-                name,
-                attributes,
-                children,
-                void,
-            }
+        let void = void || is_void_tag(&name);
+
+        move |children: E| Element::Tag {
+            name,
+            attributes,
+            children: children.into_element(bump).into_child_list(bump),
+            void,
+            key,
         }
     }
 
+    /// Create a non-void tag element from a name, attributes, and children.
+    ///
+    /// This is a direct alternative to [`Self::tag`] that takes the children up front.
+    /// See [`Element::tag_with`].
+    pub fn element(
+        &self,
+        name: &str,
+        attributes: impl IntoIterator<Item = Attribute<'bump>>,
+        children: impl IntoElement<'bump>,
+    ) -> Element<'bump> {
+        Element::tag_with(self.bump, name, attributes, children)
+    }
+
     /// Create a doctype element with a list of attributes.
     pub fn doctype(
         &self,
@@ -99,9 +247,32 @@ impl<'bump> Builder<'bump> {
             attributes: BumpVec::from_iter_in(attributes, self.bump),
             children: BumpVec::new_in(self.bump),
             void: true,
+            key: None,
         }
     }
 
+    /// Create an XML declaration (`<?xml version="1.0" encoding="UTF-8"?>`) as a raw element.
+    ///
+    /// See [`Document::new_xml`](crate::Document::new_xml) for a document that starts with one.
+    pub fn xml_declaration(&self) -> Element<'bump> {
+        self.raw(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+    }
+
+    /// Parse a snippet written in the [`html!`](crate::html) syntax into an element, ready to
+    /// be nested within other elements.
+    ///
+    /// This is a convenience wrapper around [`crate::parse_html_fragment`], so the snippet may
+    /// contain any number of top-level nodes, which are returned within a
+    /// [`Fragment`](Element::Fragment).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the snippet could not be parsed, or if it contains interpolations.
+    #[cfg(feature = "parser")]
+    pub fn fragment_from_html(&self, html: &str) -> Result<Element<'bump>, crate::ParseHtmlError> {
+        crate::parse_html_fragment(self.bump, html)
+    }
+
     /// Create a fragment element from an iterator of elements.
     ///
     /// This is a convenience wrapper around [`Element::from_iter`].
@@ -109,6 +280,24 @@ impl<'bump> Builder<'bump> {
         Element::from_iter(self.bump, children)
     }
 
+    /// Create a fragment by mapping each item of a collection to an element.
+    ///
+    /// This is a convenience wrapper around [`Element::from_iter`]:
+    /// ```
+    /// # use paxhtml::{builder::Builder, bumpalo::Bump};
+    /// # let bump = Bump::new();
+    /// # let b = Builder::new(&bump);
+    /// let items = ["a", "b"];
+    /// let list = b.ul([])(b.each(&items, |i| b.li([])(b.text(i))));
+    /// ```
+    pub fn each<I: IntoIterator, F: FnMut(I::Item) -> Element<'bump>>(
+        &self,
+        items: I,
+        f: F,
+    ) -> Element<'bump> {
+        Element::from_iter(self.bump, items.into_iter().map(f))
+    }
+
     /// Create a document from an iterator of elements.
     ///
     /// This is a convenience wrapper around [`Document::new`].
@@ -142,6 +331,10 @@ macro_rules! non_void_builders {
         }
         /// A list of all non-void tags.
         pub const NON_VOID_TAGS: &[&str] = &[$(stringify!($tag_ident)),*];
+
+        fn is_lowercase_non_void_tag(name: &str) -> bool {
+            matches!(name, $(stringify!($tag_ident))|*)
+        }
     };
 }
 non_void_builders! {
@@ -149,7 +342,7 @@ non_void_builders! {
     ol, ul, li, strong, em, blockquote, article, section,
     aside, span, script, title, time, html, a,
     h1, h2, h3, h4, h5, h6, small, sup, sub, label, q, s,
-    table, tr, td, th, tbody, thead, tfoot, colgroup, video
+    table, tr, td, th, tbody, thead, tfoot, colgroup, video, template
 }
 
 macro_rules! void_builders {
@@ -167,9 +360,303 @@ macro_rules! void_builders {
         }
         /// A list of all void tags.
         pub const VOID_TAGS: &[&str] = &[$(stringify!($tag_ident)),*];
+
+        fn is_lowercase_void_tag(name: &str) -> bool {
+            matches!(name, $(stringify!($tag_ident))|*)
+        }
     };
 }
 void_builders! {
     area, base, br, col, embed, hr, input, link, meta,
     param, source, track, wbr, img
 }
+
+macro_rules! mathml_builders {
+    ($($tag_ident:ident),*) => {
+        impl<'bump> Builder<'bump> {
+            $(
+                #[doc = concat!("Create a MathML element with the tag name `", stringify!($tag_ident), "` and a list of attributes.\n\nThe children are passed in as a single argument to the returned function.")]
+                pub fn $tag_ident<E: IntoElement<'bump>>(
+                    &self,
+                    attributes: impl IntoIterator<Item = Attribute<'bump>>,
+                ) -> impl FnOnce(E) -> Element<'bump> {
+                    self.tag(stringify!($tag_ident), attributes, false)
+                }
+            )*
+        }
+        /// A list of all MathML tags with [`Builder`] methods. These are all non-void.
+        pub const MATHML_TAGS: &[&str] = &[$(stringify!($tag_ident)),*];
+
+        fn is_lowercase_mathml_tag(name: &str) -> bool {
+            matches!(name, $(stringify!($tag_ident))|*)
+        }
+    };
+}
+mathml_builders! {
+    math, mrow, mi, mo, mn, ms, mtext, mspace, mfrac, msqrt, mroot,
+    msub, msup, msubsup, munder, mover, munderover, mtable, mtr, mtd,
+    mstyle, mpadded, mphantom, semantics, annotation
+}
+
+macro_rules! inline_tags {
+    ($($tag:literal),*) => {
+        /// A list of all tags rendered inline, which are not placed on their own line
+        /// when pretty-printing.
+        pub const INLINE_TAGS: &[&str] = &[$($tag),*];
+
+        pub(crate) fn is_inline_tag(name: &str) -> bool {
+            matches!(name, $($tag)|*)
+        }
+    };
+}
+inline_tags! {
+    "a", "abbr", "acronym", "b", "bdo", "big", "br", "button", "cite", "code", "dfn",
+    "em", "i", "img", "input", "kbd", "label", "map", "pre", "object", "output", "q",
+    "samp", "script", "select", "small", "span", "strong", "sub", "sup", "textarea",
+    "time", "tt", "var"
+}
+
+/// Returns `true` if `name` is a void tag (one of [`VOID_TAGS`]), which has no children
+/// or closing tag. Tag names are matched case-insensitively.
+pub fn is_void_tag(name: &str) -> bool {
+    with_lowercase(name, is_lowercase_void_tag)
+}
+
+/// Returns `true` if `name` is one of [`VOID_TAGS`], [`NON_VOID_TAGS`] or [`MATHML_TAGS`]
+/// (i.e. it has a [`Builder`] method). Tag names are matched case-insensitively.
+pub fn is_known_tag(name: &str) -> bool {
+    with_lowercase(name, |name| {
+        is_lowercase_void_tag(name)
+            || is_lowercase_non_void_tag(name)
+            || is_lowercase_mathml_tag(name)
+    })
+}
+
+// Calls `f` with `name` in lowercase, only allocating if it is not already lowercase
+fn with_lowercase(name: &str, f: impl Fn(&str) -> bool) -> bool {
+    if name.bytes().any(|b| b.is_ascii_uppercase()) {
+        f(&name.to_ascii_lowercase())
+    } else {
+        f(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RenderElement;
+
+    #[test]
+    fn element_matches_curried_tag() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let curried = b.document([b.div([b.attr(("class", "container"))])([
+            b.h1([])(b.text("Title")),
+            b.fragment([b.p([])(b.text("One")), b.p([])(b.text("Two"))]),
+            b.span([])(Element::Empty),
+        ])]);
+        let direct = b.document([b.element(
+            "div",
+            [b.attr(("class", "container"))],
+            [
+                b.element("h1", [], b.text("Title")),
+                b.fragment([
+                    Element::tag_with(&bump, "p", [], "One"),
+                    Element::tag_with(&bump, "p", [], "Two"),
+                ]),
+                b.element("span", [], Element::Empty),
+            ],
+        )]);
+
+        assert_eq!(direct.children, curried.children);
+        assert_eq!(
+            direct.write_to_string().unwrap(),
+            curried.write_to_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn attr_list_preserves_order() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let div = b.div(b.attr_list(&[("class", "x"), ("id", "y"), ("title", "z")]))([]);
+        let input = b.input(b.attr_list(&[("type", Some("checkbox")), ("checked", None)]));
+        let doc = b.document([div, input]);
+
+        assert_eq!(
+            doc.write_compact_to_string().unwrap(),
+            r#"<div class="x" id="y" title="z"></div><input type="checkbox" checked>"#
+        );
+    }
+
+    #[test]
+    fn tag_keyed_is_queryable_but_not_rendered() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let item = b.tag_keyed("li", "item-1", [b.attr(("class", "item"))], false)("One");
+        assert_eq!(item.key(), Some("item-1"));
+        assert_eq!(b.li([])("Two").key(), None);
+
+        let doc = b.document([b.ul([])(item)]);
+        assert_eq!(doc.children[0].key(), None);
+        let RenderElement::Tag { children, .. } = &doc.children[0] else {
+            panic!("Expected tag element");
+        };
+        assert_eq!(children[0].key(), Some("item-1"));
+        assert_eq!(
+            doc.write_compact_to_string().unwrap(),
+            r#"<ul><li class="item">One</li></ul>"#
+        );
+    }
+
+    #[test]
+    fn classes_and_style() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let element = b.div([
+            b.classes(&["a", "", "b"]),
+            b.style(&[("color", "red"), ("", "x"), ("margin", "0 auto")]),
+        ])([]);
+        assert_eq!(
+            element.to_string(),
+            r#"<div class="a b" style="color: red; margin: 0 auto"></div>"#
+        );
+    }
+
+    #[test]
+    fn classes_if_toggles_classes() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let class_of = |active: bool, disabled: bool| {
+            b.classes_if(&[("button", true), ("active", active), ("disabled", disabled)])
+                .value_as_str()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(class_of(false, false), "button");
+        assert_eq!(class_of(true, false), "button active");
+        assert_eq!(class_of(true, true), "button active disabled");
+        let none = b.classes_if(&[("a", false), ("b", false)]);
+        assert_eq!(none.key.as_str(), "class");
+        assert_eq!(none.value_as_str(), Some(""));
+    }
+
+    #[test]
+    fn tag_lookups() {
+        assert!(is_void_tag("br"));
+        assert!(is_void_tag("IMG"));
+        assert!(!is_void_tag("div"));
+        assert!(!is_void_tag("custom-element"));
+
+        assert!(is_known_tag("br"));
+        assert!(is_known_tag("div"));
+        assert!(is_known_tag("Div"));
+        assert!(!is_known_tag("custom-element"));
+
+        assert!(VOID_TAGS.iter().all(|t| is_void_tag(t) && is_known_tag(t)));
+        assert!(NON_VOID_TAGS
+            .iter()
+            .all(|t| !is_void_tag(t) && is_known_tag(t)));
+    }
+
+    #[test]
+    fn optional_attrs_drops_none() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let element = b.input(b.optional_attrs([
+            Some(b.attr(("type", "checkbox"))),
+            None,
+            Some(b.attr("checked")),
+            None,
+        ]));
+        assert_eq!(element.to_string(), r#"<input type="checkbox" checked>"#);
+    }
+
+    #[cfg(feature = "parser")]
+    #[test]
+    fn fragment_from_html_accepts_siblings() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let fragment = b.fragment_from_html(r#"<p>"a"</p><p>"b"</p>"#).unwrap();
+        let Element::Fragment { children } = &fragment else {
+            panic!("expected fragment");
+        };
+        assert_eq!(children.len(), 2);
+        assert_eq!(
+            b.div([])(fragment).to_string(),
+            "<div>\n  <p>a</p>\n  <p>b</p>\n</div>"
+        );
+
+        assert!(b.fragment_from_html(r#"<p>"a"</div>"#).is_err());
+    }
+
+    #[test]
+    fn tag_detects_void_elements() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let element = b.tag("br", [], false)([]);
+        assert!(matches!(element, Element::Tag { void: true, .. }));
+        assert_eq!(element.to_string(), "<br>");
+        assert_eq!(b.tag("BR", [], false)([]).to_string(), "<BR>");
+        assert_eq!(b.tag("div", [], false)([]).to_string(), "<div></div>");
+    }
+
+    #[test]
+    fn each_maps_items_to_fragment() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let items = vec!["One".to_string(), "Two".to_string(), "Three".to_string()];
+
+        let list = b.ul([])(b.each(&items, |i| b.li([])(b.text(i))));
+        assert_eq!(list.children().len(), 3);
+        assert_eq!(
+            list.to_string(),
+            "<ul>\n  <li>One</li>\n  <li>Two</li>\n  <li>Three</li>\n</ul>"
+        );
+    }
+
+    #[test]
+    fn builds_mathml_fraction() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let fraction = b.math([])(b.mfrac([])([b.mn([])(b.text("1")), b.mn([])(b.text("2"))]));
+
+        let document = b.document([fraction]);
+        assert_eq!(
+            document.write_compact_to_string().unwrap(),
+            "<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>"
+        );
+        assert!(MATHML_TAGS.contains(&"mfrac"));
+        assert!(MATHML_TAGS
+            .iter()
+            .all(|t| !is_void_tag(t) && is_known_tag(t)));
+    }
+
+    #[test]
+    fn raw_comment_emits_conditional_comments_verbatim() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let email = b.document([
+            b.raw_comment(r#"[if mso]><table role="presentation" width="600"><tr><td><![endif]"#),
+            b.div([b.attr(("class", "container"))])(b.text("Hello & welcome")),
+            b.raw_comment("[if mso]></td></tr></table><![endif]"),
+        ]);
+
+        assert_eq!(
+            email.write_compact_to_string().unwrap(),
+            concat!(
+                r#"<!--[if mso]><table role="presentation" width="600"><tr><td><![endif]-->"#,
+                r#"<div class="container">Hello &amp; welcome</div>"#,
+                "<!--[if mso]></td></tr></table><![endif]-->"
+            )
+        );
+    }
+}
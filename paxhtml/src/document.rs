@@ -1,9 +1,58 @@
+#[cfg(feature = "std")]
 use std::path::Path;
 
+use alloc::string::{String, ToString};
+
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 
-use crate::{builder::Builder, routing::RoutePath, Element, RenderElement};
+use crate::{builder::Builder, Element, RenderElement, RenderOptions};
+#[cfg(feature = "std")]
+use crate::{render_element::ChunkSink, routing::RoutePath};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options for [`Document::new_page_with_options`].
+pub struct PageOptions {
+    /// The `content` of the `<meta name="viewport">` tag, or `None` to omit it.
+    ///
+    /// Defaults to `width=device-width, initial-scale=1`.
+    pub viewport: Option<String>,
+}
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self {
+            viewport: Some("width=device-width, initial-scale=1".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Statistics about a render, returned by [`Document::write_with_stats`].
+pub struct RenderStats {
+    /// The number of nodes (tags, text and raw HTML) rendered.
+    pub nodes: usize,
+    /// The number of bytes written.
+    pub bytes_written: usize,
+}
+
+/// Counts the bytes written to another writer.
+#[cfg(feature = "std")]
+struct CountingWriter<'a, W: ?Sized> {
+    inner: &'a mut W,
+    bytes_written: usize,
+}
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> std::io::Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes_written += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
 
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -13,6 +62,12 @@ pub struct Document<'bump> {
     /// is created.
     pub children: BumpVec<'bump, RenderElement<'bump>>,
 }
+impl core::fmt::Display for Document<'_> {
+    /// Renders the document with [`RenderOptions::pretty`], as with [`Document::write_to_string`].
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.format_with_options(f, &RenderOptions::pretty())
+    }
+}
 impl<'bump> Document<'bump> {
     /// Create a new document with a list of children. [Element]s are converted to [RenderElement]s
     /// when the document is created.
@@ -30,9 +85,186 @@ impl<'bump> Document<'bump> {
         Self::new(bump, [b.doctype([b.attr("html")]), element])
     }
 
+    /// Parse a plain HTML document, such as one written by [`Self::write_to_string`].
+    ///
+    /// The string may contain multiple top-level nodes, including a leading
+    /// `<!DOCTYPE html>`. See [`crate::parse_plain_html`] for the supported syntax.
+    #[cfg(feature = "parser")]
+    pub fn from_html(bump: &'bump Bump, html: &str) -> Result<Self, crate::ParseHtmlError> {
+        Ok(Self::new(bump, [crate::parse_plain_html(bump, html)?]))
+    }
+
+    /// Create a new XML document, with an [XML declaration](Builder::xml_declaration)
+    /// followed by the given children.
+    ///
+    /// Render the document with [`RenderOptions::xml`], which self-closes elements if and
+    /// only if they are empty. HTML void elements are not special-cased there, so that e.g.
+    /// an RSS `<link>` can contain text.
+    pub fn new_xml(bump: &'bump Bump, children: impl IntoIterator<Item = Element<'bump>>) -> Self {
+        let b = Builder::new(bump);
+        Self::new(bump, core::iter::once(b.xml_declaration()).chain(children))
+    }
+
+    /// Create a new HTML page with the default [`PageOptions`].
+    ///
+    /// See [`Self::new_page_with_options`].
+    pub fn new_page(
+        bump: &'bump Bump,
+        head_children: impl IntoIterator<Item = Element<'bump>>,
+        body_children: impl IntoIterator<Item = Element<'bump>>,
+    ) -> Self {
+        Self::new_page_with_options(bump, &PageOptions::default(), head_children, body_children)
+    }
+
+    /// Create a new HTML page consisting of a doctype declaration, followed by an `<html>`
+    /// element containing the given `<head>` and `<body>` children.
+    ///
+    /// `<meta charset="utf-8">` is always inserted as the first child of `<head>`, followed by
+    /// the viewport `<meta>` tag if [`PageOptions::viewport`] is set.
+    pub fn new_page_with_options(
+        bump: &'bump Bump,
+        options: &PageOptions,
+        head_children: impl IntoIterator<Item = Element<'bump>>,
+        body_children: impl IntoIterator<Item = Element<'bump>>,
+    ) -> Self {
+        let b = Builder::new(bump);
+        let charset = b.meta([b.attr(("charset", "utf-8"))]);
+        let viewport = options
+            .viewport
+            .as_deref()
+            .map(|content| b.meta([b.attr(("name", "viewport")), b.attr(("content", content))]));
+
+        let head = b.head([])(
+            b.fragment(
+                [charset, viewport.unwrap_or_default()]
+                    .into_iter()
+                    .chain(head_children),
+            ),
+        );
+        let body = b.body([])(b.fragment(body_children));
+        Self::new_with_doctype(bump, b.html([])([head, body]))
+    }
+
+    /// Write the document to a [`core::fmt::Write`] using the given [`RenderOptions`].
+    ///
+    /// This is like [`Self::write_fmt_with_options`], but returns a [`core::fmt::Result`],
+    /// and is available without the `std` feature. Rendering errors, such as a void element
+    /// with children, are reported as [`core::fmt::Error`].
+    pub fn format_with_options(
+        &self,
+        f: &mut dyn core::fmt::Write,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        RenderElement::format_many_with_options(f, self.children.as_slice(), 0, options)
+    }
+}
+#[cfg(feature = "std")]
+impl<'bump> Document<'bump> {
     /// Write the document to a writer.
+    ///
+    /// This is an alias for [`Self::write_pretty`].
     pub fn write(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
-        RenderElement::write_many(writer, self.children.as_slice(), 0)?;
+        self.write_pretty(writer)
+    }
+
+    /// Write the document to a writer as with [`Self::write`], returning the number of
+    /// nodes rendered and bytes written.
+    ///
+    /// This is useful for finding unexpectedly large pages. The counting is kept out of
+    /// [`Self::write`] so that it has no overhead there.
+    pub fn write_with_stats(
+        &self,
+        writer: &mut impl std::io::Write,
+    ) -> std::io::Result<RenderStats> {
+        fn count_nodes(elements: &[RenderElement]) -> usize {
+            elements
+                .iter()
+                .map(|element| match element {
+                    RenderElement::Tag { children, .. } => 1 + count_nodes(children),
+                    RenderElement::Text { .. } | RenderElement::Raw { .. } => 1,
+                })
+                .sum()
+        }
+
+        let mut writer = CountingWriter {
+            inner: writer,
+            bytes_written: 0,
+        };
+        self.write(&mut writer)?;
+        Ok(RenderStats {
+            nodes: count_nodes(&self.children),
+            bytes_written: writer.bytes_written,
+        })
+    }
+
+    /// Write the document to a writer with block-level elements indented.
+    pub fn write_pretty(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_with_options(writer, &RenderOptions::pretty())
+    }
+
+    /// Write the document to a writer with no whitespace inserted between elements.
+    pub fn write_compact(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_with_options(writer, &RenderOptions::compact())
+    }
+
+    /// Write the document to a writer using the given [`RenderOptions`].
+    pub fn write_with_options(
+        &self,
+        writer: &mut impl std::io::Write,
+        options: &RenderOptions,
+    ) -> std::io::Result<()> {
+        RenderElement::write_many_with_options(writer, self.children.as_slice(), 0, options)?;
+        Ok(())
+    }
+
+    /// Write the document to a writer as if it were nested `depth` levels deep, so that
+    /// its indentation lines up when embedded in already-indented output.
+    ///
+    /// [`Self::write`] is equivalent to a depth of 0.
+    pub fn write_at_depth(
+        &self,
+        writer: &mut impl std::io::Write,
+        depth: usize,
+    ) -> std::io::Result<()> {
+        RenderElement::write_many_with_options(
+            writer,
+            self.children.as_slice(),
+            depth,
+            &RenderOptions::pretty(),
+        )?;
+        Ok(())
+    }
+
+    /// Write the document to a [`std::fmt::Write`], such as a [`String`], with block-level
+    /// elements indented.
+    ///
+    /// This produces the same output as [`Self::write`], without going through bytes.
+    pub fn write_fmt(&self, f: &mut dyn std::fmt::Write) -> std::io::Result<()> {
+        self.write_fmt_with_options(f, &RenderOptions::pretty())
+    }
+
+    /// Write the document to a [`std::fmt::Write`] using the given [`RenderOptions`].
+    pub fn write_fmt_with_options(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        options: &RenderOptions,
+    ) -> std::io::Result<()> {
+        RenderElement::write_many_fmt_with_options(f, self.children.as_slice(), 0, options)?;
+        Ok(())
+    }
+
+    /// Render the document piece by piece, passing each chunk of HTML to `f` as it is
+    /// produced, instead of buffering the whole output.
+    ///
+    /// The concatenation of the chunks is identical to the output of
+    /// [`Self::write_with_options`]. Returning an error from `f` stops rendering and
+    /// returns that error.
+    pub fn for_each_chunk(
+        &self,
+        options: &RenderOptions,
+        f: impl FnMut(&str) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        RenderElement::write_many_to_sink(&mut ChunkSink(f), self.children.as_slice(), 0, options)?;
         Ok(())
     }
 
@@ -56,11 +288,103 @@ impl<'bump> Document<'bump> {
         self.write(&mut route_path.writer(output_dir)?)
     }
 
+    /// Write each document to its route in `output_dir`, as with [`Document::write_to_route`].
+    ///
+    /// A failure to write one route does not stop the remaining routes from being written;
+    /// instead, each failing route is returned alongside its error.
+    pub fn write_all_routes(
+        output_dir: &Path,
+        routes: impl IntoIterator<Item = (RoutePath, Document<'bump>)>,
+    ) -> Vec<(RoutePath, std::io::Error)> {
+        routes
+            .into_iter()
+            .filter_map(|(route_path, document)| {
+                document
+                    .write_to_route(output_dir, route_path.clone())
+                    .err()
+                    .map(|error| (route_path, error))
+            })
+            .collect()
+    }
+
+    /// Render the document into an HTTP response with a `Content-Type` of
+    /// `text/html; charset=utf-8`.
+    ///
+    /// The document is rendered eagerly with [`Document::write_to_string`], so the response
+    /// owns its body and does not borrow from the bump allocator.
+    #[cfg(feature = "http")]
+    pub fn into_http_response(self) -> std::io::Result<http::Response<String>> {
+        let mut response = http::Response::new(self.write_to_string()?);
+        response.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+        Ok(response)
+    }
+
+    /// Compare the pretty-printed rendering of this document with `other`'s, returning a
+    /// line-oriented description of the first differing line, or `None` if they are the same.
+    ///
+    /// The differing lines are prefixed with `-` (from `self`) and `+` (from `other`), and
+    /// are preceded by up to two lines of context. This is intended for reporting snapshot
+    /// test failures.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if either document could not be rendered.
+    #[cfg(feature = "test-utils")]
+    pub fn diff(&self, other: &Document) -> std::io::Result<Option<String>> {
+        const CONTEXT: usize = 2;
+
+        let (ours, theirs) = (self.write_to_string()?, other.write_to_string()?);
+        let (ours, theirs): (Vec<_>, Vec<_>) = (ours.lines().collect(), theirs.lines().collect());
+        let Some(index) = (0..ours.len().max(theirs.len())).find(|&i| ours.get(i) != theirs.get(i))
+        else {
+            return Ok(None);
+        };
+
+        let mut diff = format!("first difference at line {}:\n", index + 1);
+        for line in &ours[index.saturating_sub(CONTEXT)..index] {
+            diff += &format!("  {line}\n");
+        }
+        if let Some(line) = ours.get(index) {
+            diff += &format!("- {line}\n");
+        }
+        if let Some(line) = theirs.get(index) {
+            diff += &format!("+ {line}\n");
+        }
+        Ok(Some(diff))
+    }
+
     /// Write the document to a string.
     pub fn write_to_string(&self) -> std::io::Result<String> {
-        let mut output = vec![];
-        self.write(&mut output)?;
-        Ok(String::from_utf8(output).unwrap())
+        self.write_pretty_to_string()
+    }
+
+    /// Write the document to a string with block-level elements indented.
+    pub fn write_pretty_to_string(&self) -> std::io::Result<String> {
+        self.write_to_string_with_options(&RenderOptions::pretty())
+    }
+
+    /// Write the document to a string with no whitespace inserted between elements.
+    pub fn write_compact_to_string(&self) -> std::io::Result<String> {
+        self.write_to_string_with_options(&RenderOptions::compact())
+    }
+
+    /// Write the document to a string using the given [`RenderOptions`].
+    pub fn write_to_string_with_options(&self, options: &RenderOptions) -> std::io::Result<String> {
+        let mut output = String::new();
+        self.write_fmt_with_options(&mut output, options)?;
+        Ok(output)
+    }
+
+    /// Write the document to a string, and then escape the result so that it can be
+    /// embedded as text, such as in the `<description>` of an RSS feed.
+    ///
+    /// `<`, `>`, `&`, `"` and `'` are all escaped.
+    pub fn write_escaped_to_string(&self) -> std::io::Result<String> {
+        let html = self.write_to_string()?;
+        Ok(html_escape::encode_quoted_attribute(&html).into_owned())
     }
 }
 
@@ -126,4 +450,375 @@ mod tests {
             "<!DOCTYPE html><html>\n  <body>Hello</body>\n</html>"
         );
     }
+
+    #[test]
+    fn test_write_pretty_and_compact() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let input = b.document_with_doctype(b.html([])([
+            b.head([])(b.title([])(b.text("Title"))),
+            b.body([])(b.p([])(b.text("Hello"))),
+        ]));
+
+        assert_eq!(
+            input.write_pretty_to_string().unwrap(),
+            "<!DOCTYPE html><html>\n  <head>\n    <title>Title</title>\n  </head>\n  <body>\n    <p>Hello</p>\n  </body>\n</html>"
+        );
+        assert_eq!(
+            input.write_compact_to_string().unwrap(),
+            "<!DOCTYPE html><html><head><title>Title</title></head><body><p>Hello</p></body></html>"
+        );
+        assert_eq!(
+            input.write_to_string().unwrap(),
+            input.write_pretty_to_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_display_matches_write_to_string() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([b.attr(("class", "a & b"))])([
+            b.p([])(b.text("Hello <world>")),
+            b.p([])(b.text("Again")),
+        ]);
+        let input = b.document([element.clone()]);
+
+        let expected = input.write_to_string().unwrap();
+        assert_eq!(format!("{input}"), expected);
+        assert_eq!(format!("{element}"), expected);
+    }
+
+    #[test]
+    fn test_new_page() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let input = Document::new_page(
+            &bump,
+            [b.title([])(b.text("Title"))],
+            [b.p([])(b.text("Hello"))],
+        );
+
+        let output = input.write_to_string().unwrap();
+        assert_eq!(
+            output,
+            "<!DOCTYPE html><html>\n  <head>\n    <meta charset=\"utf-8\">\n    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n    <title>Title</title>\n  </head>\n  <body>\n    <p>Hello</p>\n  </body>\n</html>"
+        );
+    }
+
+    #[test]
+    fn test_new_page_without_viewport() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let options = PageOptions { viewport: None };
+        let input =
+            Document::new_page_with_options(&bump, &options, [b.title([])(b.text("Title"))], []);
+
+        let output = input.write_compact_to_string().unwrap();
+        assert_eq!(
+            output,
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Title</title></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn test_write_escaped_to_string() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = b.document([b.p([b.attr(("title", "it's"))])(b.text("Fish & chips"))]);
+
+        assert_eq!(
+            doc.write_escaped_to_string().unwrap(),
+            "&lt;p title=&quot;it&#x27;s&quot;&gt;Fish &amp;amp; chips&lt;/p&gt;"
+        );
+    }
+
+    #[test]
+    fn test_for_each_chunk_matches_buffered_output() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = b.document_with_doctype(b.html([])([
+            b.head([])(b.title([])(b.text("Chunks"))),
+            b.body([])([
+                b.h1([])(b.text("Title & more")),
+                b.ul([])([b.li([])(b.text("One")), b.li([])(b.em([])(b.text("Two")))]),
+                b.pre([])(b.text("  keep\n  this")),
+            ]),
+        ]));
+
+        for options in [RenderOptions::pretty(), RenderOptions::compact()] {
+            let mut chunks = vec![];
+            doc.for_each_chunk(&options, |chunk| {
+                chunks.push(chunk.to_string());
+                Ok(())
+            })
+            .unwrap();
+
+            assert!(chunks.len() > 1);
+            assert_eq!(
+                chunks.concat(),
+                doc.write_to_string_with_options(&options).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_for_each_chunk_stops_on_error() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = b.document([b.p([])(b.text("One")), b.p([])(b.text("Two"))]);
+
+        let mut received = 0;
+        let result = doc.for_each_chunk(&RenderOptions::default(), |_| {
+            received += 1;
+            Err(std::io::Error::other("disconnected"))
+        });
+
+        assert_eq!(result.unwrap_err().to_string(), "disconnected");
+        assert_eq!(received, 1);
+    }
+
+    #[test]
+    fn test_write_fmt_matches_io_output() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = b.document_with_doctype(b.html([])(b.body([])([
+            b.h1([b.attr(("title", "\"quoted\""))])(b.text("Fish & chips")),
+            b.p([])([b.text("Some "), b.em([])(b.text("text"))]),
+            b.raw("<hr>"),
+        ])));
+
+        for options in [RenderOptions::pretty(), RenderOptions::compact()] {
+            let mut bytes = vec![];
+            doc.write_with_options(&mut bytes, &options).unwrap();
+            let mut string = String::new();
+            doc.write_fmt_with_options(&mut string, &options).unwrap();
+
+            assert_eq!(string, String::from_utf8(bytes).unwrap());
+
+            let mut formatted = String::new();
+            doc.format_with_options(&mut formatted, &options).unwrap();
+            assert_eq!(formatted, string);
+        }
+        assert_eq!(doc.to_string(), doc.write_to_string().unwrap());
+    }
+
+    #[test]
+    fn test_write_fmt_reports_void_element_with_children() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = b.document([b.tag("br", [], true)(b.text("child"))]);
+
+        let mut string = String::new();
+        let error = doc.write_fmt(&mut string).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(doc
+            .format_with_options(&mut string, &RenderOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_write_all_routes() {
+        let output_dir =
+            std::env::temp_dir().join(format!("paxhtml-write-all-routes-{}", std::process::id()));
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let routes = [
+            RoutePath::new([], None),
+            RoutePath::new(["blog", "post"], None),
+        ];
+
+        let errors = Document::write_all_routes(
+            &output_dir,
+            routes
+                .iter()
+                .map(|route| (route.clone(), b.document([b.p([])(b.text("hi"))]))),
+        );
+        assert!(errors.is_empty());
+        for route in &routes {
+            assert_eq!(
+                std::fs::read_to_string(route.file_path(&output_dir)).unwrap(),
+                "<p>hi</p>"
+            );
+        }
+
+        // A file in place of a route's directory fails only that route
+        std::fs::write(output_dir.join("blocked"), "").unwrap();
+        let errors = Document::write_all_routes(
+            &output_dir,
+            [
+                (RoutePath::new(["blocked"], None), b.document([])),
+                (RoutePath::new(["open"], None), b.document([])),
+            ],
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, RoutePath::new(["blocked"], None));
+        assert!(RoutePath::new(["open"], None)
+            .file_path(&output_dir)
+            .exists());
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_into_http_response() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = b.document([b.p([])(b.text("Hello"))]);
+
+        let response = doc.into_http_response().unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(response.body(), "<p>Hello</p>");
+    }
+
+    #[test]
+    fn test_new_xml() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let doc = Document::new_xml(
+            &bump,
+            [b.tag("rss", [b.attr(("version", "2.0"))], false)(b.tag(
+                "channel",
+                [],
+                false,
+            )(
+                [
+                b.tag("link", [], false)(b.text("https://example.com/")),
+                b.tag("atom:link", [b.attr(("rel", "self"))], false)([]),
+                b.tag("br", [], false)([]),
+            ]
+            ))],
+        );
+        let empty = Document::new_xml(&bump, [b.tag("description", [], false)([])]);
+
+        assert_eq!(
+            empty.write_to_string().unwrap(),
+            r#"<?xml version="1.0" encoding="UTF-8"?><description></description>"#
+        );
+        assert_eq!(
+            empty
+                .write_to_string_with_options(&RenderOptions::xml())
+                .unwrap(),
+            r#"<?xml version="1.0" encoding="UTF-8"?><description/>"#
+        );
+
+        assert_eq!(
+            doc.write_to_string_with_options(&RenderOptions::xml())
+                .unwrap(),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<rss version="2.0"><channel><link>https://example.com/</link>"#,
+                r#"<atom:link rel="self"/><br/></channel></rss>"#
+            )
+        );
+        assert_eq!(
+            b.document_with_doctype(b.br([]))
+                .write_to_string_with_options(&RenderOptions::xml())
+                .unwrap(),
+            "<!DOCTYPE html><br/>"
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_diff() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let page = |class| {
+            b.document([b.div([])([
+                b.h1([])(b.text("Title")),
+                b.p([b.attr(("class", class))])(b.text("Body")),
+                b.p([])(b.text("Footer")),
+            ])])
+        };
+
+        assert_eq!(page("a").diff(&page("a")).unwrap(), None);
+        assert_eq!(
+            page("a").diff(&page("b")).unwrap().unwrap(),
+            concat!(
+                "first difference at line 3:\n",
+                "  <div>\n",
+                "    <h1>Title</h1>\n",
+                "-   <p class=\"a\">Body</p>\n",
+                "+   <p class=\"b\">Body</p>\n",
+            )
+        );
+        assert_eq!(
+            page("a")
+                .diff(&b.document([b.div([])([])]))
+                .unwrap()
+                .unwrap(),
+            "first difference at line 1:\n- <div>\n+ <div></div>\n"
+        );
+    }
+
+    #[test]
+    fn write_at_depth_indents_from_starting_depth() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let document = b.document([b.div([])(b.p([])(b.text("Nested")))]);
+
+        let mut output = vec![];
+        document.write_at_depth(&mut output, 2).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\n    <div>\n      <p>Nested</p>\n    </div>"
+        );
+
+        let mut output = vec![];
+        document.write_at_depth(&mut output, 0).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            document.write_to_string().unwrap()
+        );
+    }
+
+    #[cfg(feature = "parser")]
+    #[test]
+    fn from_html_round_trips() {
+        let bump = Bump::new();
+        let html = "<!DOCTYPE html><html lang=\"en\"><head><title>Test</title></head><body><p>Hello</p></body></html>";
+
+        let document = Document::from_html(&bump, html).unwrap();
+        assert_eq!(document.children.len(), 2);
+        assert_eq!(document.write_compact_to_string().unwrap(), html);
+
+        let pretty = document.write_to_string().unwrap();
+        assert!(pretty.starts_with("<!DOCTYPE html><html lang=\"en\">\n  <head>"));
+        assert_eq!(
+            Document::from_html(&bump, &pretty)
+                .unwrap()
+                .write_compact_to_string()
+                .unwrap(),
+            html
+        );
+
+        assert!(Document::from_html(&bump, "<div>").is_err());
+    }
+
+    #[test]
+    fn write_with_stats_counts_nodes_and_bytes() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        // html, head, title, "Stats", body, p, "Hello ", strong, "world", raw
+        let document = b.document([b.html([])([
+            b.head([])(b.title([])(b.text("Stats"))),
+            b.body([])([
+                b.p([])([b.text("Hello "), b.strong([])(b.text("world"))]),
+                Element::Empty,
+                b.raw("<!-- end -->"),
+            ]),
+        ])]);
+
+        let mut output = vec![];
+        let stats = document.write_with_stats(&mut output).unwrap();
+        assert_eq!(stats.nodes, 10);
+        assert_eq!(stats.bytes_written, output.len());
+        assert_eq!(output, document.write_to_string().unwrap().into_bytes());
+    }
 }
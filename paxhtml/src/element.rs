@@ -1,14 +1,21 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
 use bumpalo::collections::String as BumpString;
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 
-use crate::Attribute;
+use crate::selector::{Selector, SelectorError};
+use crate::{Attribute, RenderElement};
 
 /// An element in an HTML document. This is optimised for authoring, and supports both
 /// [Element::Empty] and [Element::Fragment] for convenience.
 ///
 /// These will be removed when converted to [crate::RenderElement]s.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Element<'bump> {
@@ -25,6 +32,10 @@ pub enum Element<'bump> {
         children: BumpVec<'bump, Element<'bump>>,
         /// Whether the tag is void.
         void: bool,
+        /// An optional key identifying this element across renders, for use by diffing
+        /// tools. This is not rendered.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        key: Option<BumpString<'bump>>,
     },
     /// A fragment element.
     Fragment {
@@ -43,6 +54,24 @@ pub enum Element<'bump> {
     },
 }
 
+impl core::fmt::Display for Element<'_> {
+    /// Renders the element as it would appear in a [`crate::Document`], using
+    /// [`crate::RenderOptions::pretty`].
+    ///
+    /// The element is copied into a temporary bump allocator to convert it into
+    /// [`RenderElement`]s; see [`RenderElement::from_element_ref`].
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let bump = Bump::new();
+        let elements = RenderElement::from_element_ref(&bump, self);
+        RenderElement::format_many_with_options(
+            f,
+            elements.as_slice(),
+            0,
+            &crate::RenderOptions::default(),
+        )
+    }
+}
+
 /// Trait for types that can provide a default value given a bump allocator.
 ///
 /// This is used by the `html!` macro to support default prop values for custom components.
@@ -70,6 +99,33 @@ pub trait DefaultIn<'bump> {
     fn default_in(bump: &'bump Bump) -> Self;
 }
 
+/// Trait for the types that the `children` field of a custom component's props can have.
+///
+/// [`html!`](crate::html) converts the children of a custom component into a single
+/// [`Element`] with [`Element::from_iter`], and then converts that into the field's type:
+/// - `Element` receives it directly;
+/// - `Option<Element>` receives it wrapped in `Some`;
+/// - `BumpVec<Element>` receives its top-level children, with fragments flattened.
+pub trait FromChildren<'bump> {
+    /// Convert the children of a component into this type.
+    fn from_children(bump: &'bump Bump, children: Element<'bump>) -> Self;
+}
+impl<'bump> FromChildren<'bump> for Element<'bump> {
+    fn from_children(_bump: &'bump Bump, children: Element<'bump>) -> Self {
+        children
+    }
+}
+impl<'bump> FromChildren<'bump> for Option<Element<'bump>> {
+    fn from_children(_bump: &'bump Bump, children: Element<'bump>) -> Self {
+        Some(children)
+    }
+}
+impl<'bump> FromChildren<'bump> for BumpVec<'bump, Element<'bump>> {
+    fn from_children(bump: &'bump Bump, children: Element<'bump>) -> Self {
+        children.into_child_list(bump)
+    }
+}
+
 /// Trait for types that can be converted into an Element with a bump allocator.
 pub trait IntoElement<'bump> {
     /// Convert this value into an Element using the given bump allocator.
@@ -109,6 +165,18 @@ impl<'bump, T: IntoElement<'bump>> IntoElement<'bump> for Option<T> {
         }
     }
 }
+/// `Ok` values are converted as usual, while `Err` values are rendered as a text node
+/// containing the error's [`Display`](std::fmt::Display) output.
+impl<'bump, T: IntoElement<'bump>, E: core::fmt::Display> IntoElement<'bump> for Result<T, E> {
+    fn into_element(self, bump: &'bump Bump) -> Element<'bump> {
+        match self {
+            Ok(e) => e.into_element(bump),
+            Err(e) => Element::Text {
+                text: bumpalo::format!(in bump, "{}", e),
+            },
+        }
+    }
+}
 impl<'bump, const N: usize> IntoElement<'bump> for [Element<'bump>; N] {
     fn into_element(self, bump: &'bump Bump) -> Element<'bump> {
         if N == 0 {
@@ -138,6 +206,93 @@ impl<'bump> Element<'bump> {
         }
     }
 
+    /// Create a tag element with its children provided up front.
+    ///
+    /// The tag is void if [`crate::builder::is_void_tag`] returns `true` for `name`.
+    ///
+    /// A [`Fragment`](Element::Fragment) passed as `children` is flattened into the tag,
+    /// and [`Empty`](Element::Empty) results in no children, matching the function returned
+    /// by [`crate::builder::Builder::tag`].
+    pub fn tag_with(
+        bump: &'bump Bump,
+        name: &str,
+        attributes: impl IntoIterator<Item = Attribute<'bump>>,
+        children: impl IntoElement<'bump>,
+    ) -> Element<'bump> {
+        Element::Tag {
+            name: BumpString::from_str_in(name, bump),
+            attributes: BumpVec::from_iter_in(attributes, bump),
+            children: children.into_element(bump).into_child_list(bump),
+            void: crate::builder::is_void_tag(name),
+            key: None,
+        }
+    }
+
+    /// Wrap this element in a new tag, making it the tag's sole child.
+    ///
+    /// Unlike [`Element::tag_with`], a [`Fragment`](Element::Fragment) is kept intact as a
+    /// single child rather than flattened. Wrapping [`Empty`](Element::Empty) results in a
+    /// tag with no children.
+    pub fn wrap(
+        self,
+        bump: &'bump Bump,
+        name: &str,
+        attributes: impl IntoIterator<Item = Attribute<'bump>>,
+    ) -> Element<'bump> {
+        Element::wrap_many(bump, name, attributes, [self])
+    }
+
+    /// Wrap the given elements in a new tag, making them its children in order.
+    ///
+    /// As with [`Element::wrap`], fragments are kept intact as single children, while
+    /// [`Empty`](Element::Empty) elements are dropped.
+    pub fn wrap_many(
+        bump: &'bump Bump,
+        name: &str,
+        attributes: impl IntoIterator<Item = Attribute<'bump>>,
+        elements: impl IntoIterator<Item = Element<'bump>>,
+    ) -> Element<'bump> {
+        Element::Tag {
+            name: BumpString::from_str_in(name, bump),
+            attributes: BumpVec::from_iter_in(attributes, bump),
+            children: BumpVec::from_iter_in(elements.into_iter().filter(|e| !e.is_empty()), bump),
+            void: crate::builder::is_void_tag(name),
+            key: None,
+        }
+    }
+
+    /// Create a raw HTML element, after checking that `html` is well-formed.
+    ///
+    /// The HTML is checked with [`paxhtml_parser::parse_plain_html`], but is stored as-is,
+    /// so it is neither re-escaped nor reformatted when rendered. Use [`Element::raw`] for
+    /// trusted content that does not need to be checked.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `html` is not well-formed, such as when a tag is left unclosed.
+    #[cfg(feature = "parser")]
+    pub fn raw_checked(
+        bump: &'bump Bump,
+        html: &str,
+    ) -> Result<Element<'bump>, crate::ParseHtmlError> {
+        paxhtml_parser::parse_plain_html(html)?;
+        Ok(Element::raw(bump, html))
+    }
+
+    /// Convert this element into a list of children for a tag, flattening fragments and
+    /// dropping [`Empty`](Element::Empty).
+    pub(crate) fn into_child_list(self, bump: &'bump Bump) -> BumpVec<'bump, Element<'bump>> {
+        match self {
+            Element::Fragment { children } => children,
+            Element::Empty => BumpVec::new_in(bump),
+            other => {
+                let mut v = BumpVec::with_capacity_in(1, bump);
+                v.push(other);
+                v
+            }
+        }
+    }
+
     /// Create a text element.
     pub fn text(bump: &'bump Bump, text: &str) -> Element<'bump> {
         Element::Text {
@@ -179,6 +334,92 @@ impl<'bump> Element<'bump> {
         }
     }
 
+    /// Get the key of the element if it is a [`Tag`](Element::Tag) with a key.
+    ///
+    /// Keys are metadata for tools that match elements across renders, and are not rendered.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Element::Tag { key, .. } => key.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Set an attribute on a [`Tag`](Element::Tag), replacing the first existing attribute
+    /// with the same key (matched case-insensitively) and returning it. If there is none,
+    /// the attribute is appended.
+    ///
+    /// This is a no-op on other variants.
+    pub fn set_attr(&mut self, attribute: Attribute<'bump>) -> Option<Attribute<'bump>> {
+        let Element::Tag { attributes, .. } = self else {
+            return None;
+        };
+        match attributes
+            .iter_mut()
+            .find(|a| a.key.eq_ignore_ascii_case(&attribute.key))
+        {
+            Some(existing) => Some(core::mem::replace(existing, attribute)),
+            None => {
+                attributes.push(attribute);
+                None
+            }
+        }
+    }
+
+    /// Remove the first attribute with the given key (matched case-insensitively) from a
+    /// [`Tag`](Element::Tag), returning it if it was present.
+    ///
+    /// This is a no-op on other variants.
+    pub fn remove_attr(&mut self, key: &str) -> Option<Attribute<'bump>> {
+        let Element::Tag { attributes, .. } = self else {
+            return None;
+        };
+        let index = attributes
+            .iter()
+            .position(|a| a.key.eq_ignore_ascii_case(key))?;
+        Some(attributes.remove(index))
+    }
+
+    /// Toggle a boolean attribute on a [`Tag`](Element::Tag): it is removed if present, and
+    /// added without a value otherwise.
+    ///
+    /// This is a no-op on other variants.
+    pub fn toggle_attr(&mut self, bump: &'bump Bump, key: &str) {
+        if self.remove_attr(key).is_none() {
+            if let Element::Tag { attributes, .. } = self {
+                attributes.push(Attribute::boolean(bump, key));
+            }
+        }
+    }
+
+    /// Find all tags in the tree matching a CSS selector, in document order.
+    ///
+    /// The element itself is included if it matches. Supported selectors are type (`div`),
+    /// class (`.card`) and ID (`#main`) selectors, compounds of these (`div.card`), and the
+    /// descendant combinator (`#main p`). Classes match any entry of the space-separated
+    /// `class` attribute, and tag names match case-insensitively.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the selector is empty or uses unsupported syntax.
+    pub fn select(&self, selector: &str) -> Result<Vec<&Element<'bump>>, SelectorError> {
+        let selector = Selector::parse(selector)?;
+        let mut results = vec![];
+        selector.select(self, &mut vec![], &mut results);
+        Ok(results)
+    }
+
+    /// Whether this element matches a CSS selector, as supported by [`Self::select`].
+    ///
+    /// The element is matched without any ancestors, so a selector with a descendant
+    /// combinator never matches.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the selector is empty or uses unsupported syntax.
+    pub fn matches(&self, selector: &str) -> Result<bool, SelectorError> {
+        Ok(Selector::parse(selector)?.matches(self, &[]))
+    }
+
     /// Get the inner text of the element.
     ///
     /// This will return an empty string if no inner text exists.
@@ -208,6 +449,394 @@ impl<'bump> Element<'bump> {
         }
     }
 
+    /// Get the inner text of the element as a [`String`], without needing a bump
+    /// allocator.
+    ///
+    /// This is the same text as [`Self::inner_text`].
+    pub fn inner_text_owned(&self) -> String {
+        let mut output = String::new();
+        self.write_inner_text(&mut output, None);
+        output
+    }
+
+    /// Get the inner text of the element as a [`String`], with `separator` placed between
+    /// the text of block-level elements, so that e.g. the words of adjacent paragraphs do
+    /// not run together.
+    ///
+    /// Elements that are not [inline tags](crate::builder::INLINE_TAGS) are block-level.
+    /// The separator is never repeated, or placed at the start or end of the text.
+    pub fn inner_text_with_block_separator(&self, separator: &str) -> String {
+        let mut output = String::new();
+        self.write_inner_text(&mut output, Some(separator));
+        if let Some(trimmed_len) = output.strip_suffix(separator).map(str::len) {
+            output.truncate(trimmed_len);
+        }
+        output
+    }
+
+    fn write_inner_text(&self, output: &mut String, block_separator: Option<&str>) {
+        let push_separator = |output: &mut String| {
+            if let Some(separator) = block_separator {
+                if !output.is_empty() && !output.ends_with(separator) {
+                    output.push_str(separator);
+                }
+            }
+        };
+        match self {
+            Element::Tag { name, children, .. } => {
+                let block = !crate::builder::is_inline_tag(name);
+                if block {
+                    push_separator(output);
+                }
+                for child in children.iter() {
+                    child.write_inner_text(output, block_separator);
+                }
+                if block {
+                    push_separator(output);
+                }
+            }
+            Element::Fragment { children } => {
+                for child in children.iter() {
+                    child.write_inner_text(output, block_separator);
+                }
+            }
+            Element::Text { text } => output.push_str(text.as_str()),
+            Element::Empty | Element::Raw { .. } => {}
+        }
+    }
+
+    /// Get the total number of nodes in the tree, including this one.
+    ///
+    /// Every element counts as one node, including [`Fragment`](Element::Fragment)s and
+    /// [`Empty`](Element::Empty).
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children()
+            .iter()
+            .map(Element::node_count)
+            .sum::<usize>()
+    }
+
+    /// Get the depth of the tree, where an element with no children has a depth of 1.
+    pub fn depth(&self) -> usize {
+        1 + self
+            .children()
+            .iter()
+            .map(Element::depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Estimate the size of the tree in bytes, by summing the lengths of its tag names,
+    /// attribute keys, string attribute values, text, and raw HTML.
+    ///
+    /// This does not account for markup or escaping, so it is a lower bound on the size
+    /// of the rendered output.
+    pub fn byte_size_estimate(&self) -> usize {
+        let own_size = match self {
+            Element::Tag {
+                name, attributes, ..
+            } => {
+                name.len()
+                    + attributes
+                        .iter()
+                        .map(|a| a.key.len() + a.value_as_str().map_or(0, str::len))
+                        .sum::<usize>()
+            }
+            Element::Text { text } => text.len(),
+            Element::Raw { html } => html.len(),
+            Element::Fragment { .. } | Element::Empty => 0,
+        };
+        own_size
+            + self
+                .children()
+                .iter()
+                .map(Element::byte_size_estimate)
+                .sum::<usize>()
+    }
+
+    /// Render the element to a string, as it would appear in a [`crate::Document`].
+    ///
+    /// This is like the [`Display`](std::fmt::Display) implementation, but copies the
+    /// element into `bump`, which may be a scratch allocator, and returns rendering errors
+    /// instead of failing the formatter.
+    #[cfg(feature = "std")]
+    pub fn render_to_string(&self, bump: &Bump) -> std::io::Result<String> {
+        let elements = RenderElement::from_element_ref(bump, self);
+        RenderElement::write_many_to_string(elements.as_slice())
+    }
+
+    /// Get the children of a [`Tag`](Element::Tag) or [`Fragment`](Element::Fragment).
+    ///
+    /// Other variants have no children, and return an empty slice.
+    pub fn children(&self) -> &[Element<'bump>] {
+        match self {
+            Element::Tag { children, .. } | Element::Fragment { children } => children,
+            _ => &[],
+        }
+    }
+
+    /// Take the children of a [`Tag`](Element::Tag) or [`Fragment`](Element::Fragment).
+    ///
+    /// Other variants have no children, and return an empty list.
+    pub fn into_children(self, bump: &'bump Bump) -> BumpVec<'bump, Element<'bump>> {
+        match self {
+            Element::Tag { children, .. } | Element::Fragment { children } => children,
+            _ => BumpVec::new_in(bump),
+        }
+    }
+
+    /// Replace every node in the tree for which `f` returns `Some`, top-down.
+    ///
+    /// `f` is called on each node before its children; if it returns a replacement, the
+    /// replacement is used as-is and its children are not visited. A replacement
+    /// [`Fragment`](Element::Fragment) is spliced into its parent as multiple siblings, and
+    /// an [`Empty`](Element::Empty) replacement removes the node.
+    pub fn replace_subtree<F: FnMut(&Element<'bump>) -> Option<Element<'bump>>>(
+        self,
+        bump: &'bump Bump,
+        mut f: F,
+    ) -> Element<'bump> {
+        match f(&self) {
+            Some(replacement) => replacement,
+            None => self.replace_descendants_with(bump, &mut f),
+        }
+    }
+
+    // Applies `replace_subtree` to the children of this node, but not the node itself
+    fn replace_descendants_with<F: FnMut(&Element<'bump>) -> Option<Element<'bump>>>(
+        self,
+        bump: &'bump Bump,
+        f: &mut F,
+    ) -> Element<'bump> {
+        let mut replace_children = |children: BumpVec<'bump, Element<'bump>>| {
+            let mut result = BumpVec::with_capacity_in(children.len(), bump);
+            for child in children {
+                // Only replacements are spliced or removed; untouched children are kept as-is
+                match f(&child) {
+                    Some(Element::Fragment { children }) => result.extend(children),
+                    Some(Element::Empty) => {}
+                    Some(replacement) => result.push(replacement),
+                    None => result.push(child.replace_descendants_with(bump, f)),
+                }
+            }
+            result
+        };
+        match self {
+            Element::Tag {
+                name,
+                attributes,
+                children,
+                void,
+                key,
+            } => Element::Tag {
+                name,
+                attributes,
+                children: replace_children(children),
+                void,
+                key,
+            },
+            Element::Fragment { children } => Element::Fragment {
+                children: replace_children(children),
+            },
+            other => other,
+        }
+    }
+
+    /// Transform every node in the tree, bottom-up.
+    ///
+    /// `f` is called on each node after its children have been transformed, and its
+    /// result replaces the node. This is called on the root last.
+    pub fn transform<F: FnMut(Element<'bump>) -> Element<'bump>>(
+        self,
+        bump: &'bump Bump,
+        mut f: F,
+    ) -> Element<'bump> {
+        self.transform_with(bump, &mut f)
+    }
+
+    fn transform_with<F: FnMut(Element<'bump>) -> Element<'bump>>(
+        self,
+        bump: &'bump Bump,
+        f: &mut F,
+    ) -> Element<'bump> {
+        let element = match self {
+            Element::Tag {
+                name,
+                attributes,
+                children,
+                void,
+                key,
+            } => Element::Tag {
+                name,
+                attributes,
+                children: BumpVec::from_iter_in(
+                    children.into_iter().map(|c| c.transform_with(bump, f)),
+                    bump,
+                ),
+                void,
+                key,
+            },
+            Element::Fragment { children } => Element::Fragment {
+                children: BumpVec::from_iter_in(
+                    children.into_iter().map(|c| c.transform_with(bump, f)),
+                    bump,
+                ),
+            },
+            other => other,
+        };
+        f(element)
+    }
+
+    /// Sort the attributes of this element and all of its descendants using
+    /// [`Attribute::sort_order`].
+    pub fn sort_attributes(&mut self) {
+        match self {
+            Element::Tag {
+                attributes,
+                children,
+                ..
+            } => {
+                attributes.sort_by(Attribute::sort_order);
+                children.iter_mut().for_each(Element::sort_attributes);
+            }
+            Element::Fragment { children } => {
+                children.iter_mut().for_each(Element::sort_attributes);
+            }
+            _ => {}
+        }
+    }
+
+    /// Append a child to a [`Tag`](Element::Tag) or [`Fragment`](Element::Fragment).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the element is a void tag, or is not a tag or fragment. The
+    /// element is left unchanged.
+    pub fn push_child(&mut self, child: Element<'bump>) -> Result<(), AddChildError> {
+        self.children_mut()?.push(child);
+        Ok(())
+    }
+
+    /// Append children to a [`Tag`](Element::Tag) or [`Fragment`](Element::Fragment).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the element is a void tag, or is not a tag or fragment. The
+    /// element is left unchanged.
+    pub fn extend_children(
+        &mut self,
+        children: impl IntoIterator<Item = Element<'bump>>,
+    ) -> Result<(), AddChildError> {
+        self.children_mut()?.extend(children);
+        Ok(())
+    }
+
+    fn children_mut(&mut self) -> Result<&mut BumpVec<'bump, Element<'bump>>, AddChildError> {
+        match self {
+            Element::Tag {
+                name, void: true, ..
+            } => Err(AddChildError::VoidElement {
+                name: name.to_string(),
+            }),
+            Element::Tag { children, .. } | Element::Fragment { children } => Ok(children),
+            _ => Err(AddChildError::NotAContainer),
+        }
+    }
+
+    /// Normalize the tree by hoisting the children of nested fragments into their parent
+    /// and removing [`Empty`](Element::Empty) nodes.
+    ///
+    /// A fragment left with no children becomes [`Empty`](Element::Empty), and a fragment
+    /// left with one child is replaced by that child. This matches how the tree is flattened
+    /// by [`RenderElement::from_elements`](crate::RenderElement::from_elements), but keeps
+    /// the result as an [`Element`].
+    pub fn flattened(self, bump: &'bump Bump) -> Element<'bump> {
+        fn flatten_children<'bump>(
+            bump: &'bump Bump,
+            children: BumpVec<'bump, Element<'bump>>,
+        ) -> BumpVec<'bump, Element<'bump>> {
+            let mut result = BumpVec::with_capacity_in(children.len(), bump);
+            for child in children {
+                result.extend(child.flattened(bump).into_child_list(bump));
+            }
+            result
+        }
+
+        match self {
+            Element::Tag {
+                name,
+                attributes,
+                children,
+                void,
+                key,
+            } => Element::Tag {
+                name,
+                attributes,
+                children: flatten_children(bump, children),
+                void,
+                key,
+            },
+            Element::Fragment { children } => {
+                let mut children = flatten_children(bump, children);
+                match children.len() {
+                    0 => Element::Empty,
+                    1 => children.pop().unwrap(),
+                    _ => Element::Fragment { children },
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Compare two elements structurally, treating the attributes of each tag as an
+    /// unordered set.
+    ///
+    /// Unlike `==`, this considers `<a href="x" id="y">` and `<a id="y" href="x">` equal.
+    /// Attributes are matched by key and value, so a boolean attribute is never equal to
+    /// a valued attribute with the same key. Element [keys](Self::key) are not compared.
+    pub fn structural_eq(&self, other: &Element) -> bool {
+        fn children_eq(a: &[Element], b: &[Element]) -> bool {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.structural_eq(b))
+        }
+        fn attributes_eq(a: &[Attribute], b: &[Attribute]) -> bool {
+            a.len() == b.len()
+                && a.iter().all(|attr| b.contains(attr))
+                && b.iter().all(|attr| a.contains(attr))
+        }
+
+        match (self, other) {
+            (Element::Empty, Element::Empty) => true,
+            (
+                Element::Tag {
+                    name: a_name,
+                    attributes: a_attributes,
+                    children: a_children,
+                    void: a_void,
+                    ..
+                },
+                Element::Tag {
+                    name: b_name,
+                    attributes: b_attributes,
+                    children: b_children,
+                    void: b_void,
+                    ..
+                },
+            ) => {
+                a_name == b_name
+                    && a_void == b_void
+                    && attributes_eq(a_attributes, b_attributes)
+                    && children_eq(a_children, b_children)
+            }
+            (Element::Fragment { children: a }, Element::Fragment { children: b }) => {
+                children_eq(a, b)
+            }
+            (Element::Text { text: a }, Element::Text { text: b }) => a == b,
+            (Element::Raw { html: a }, Element::Raw { html: b }) => a == b,
+            _ => false,
+        }
+    }
+
     /// Returns `true` if the element is [`Empty`].
     ///
     /// [`Empty`]: Element::Empty
@@ -248,3 +877,483 @@ impl<'bump> Element<'bump> {
         matches!(self, Self::Raw { .. })
     }
 }
+
+/// Error returned when adding children to an [`Element`] that cannot have them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddChildError {
+    /// The element is a void tag.
+    VoidElement {
+        /// The name of the tag.
+        name: String,
+    },
+    /// The element is not a tag or fragment.
+    NotAContainer,
+}
+impl core::fmt::Display for AddChildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AddChildError::VoidElement { name } => {
+                write!(f, "Void element '{name}' cannot have children")
+            }
+            AddChildError::NotAContainer => {
+                write!(f, "Only tags and fragments can have children")
+            }
+        }
+    }
+}
+impl core::error::Error for AddChildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+
+    #[test]
+    fn structural_eq_ignores_attribute_order() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let a = b.div([b.attr(("id", "main")), b.attr(("class", "card"))])(b
+            .a([b.attr(("href", "/")), b.attr("download")])(
+            b.text("Home")
+        ));
+        let c = b.div([b.attr(("class", "card")), b.attr(("id", "main"))])(b
+            .a([b.attr("download"), b.attr(("href", "/"))])(
+            b.text("Home")
+        ));
+
+        assert!(a != c);
+        assert!(a.structural_eq(&c));
+    }
+
+    #[test]
+    fn structural_eq_distinguishes_boolean_and_valued_attributes() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let a = b.input([b.attr("disabled"), b.attr(("name", "x"))]);
+        let c = b.input([b.attr(("name", "x")), b.attr(("disabled", "disabled"))]);
+
+        assert!(!a.structural_eq(&c));
+    }
+
+    #[test]
+    fn structural_eq_compares_children_in_order() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let a = b.ul([])([b.li([])(b.text("1")), b.li([])(b.text("2"))]);
+        let c = b.ul([])([b.li([])(b.text("2")), b.li([])(b.text("1"))]);
+
+        assert!(!a.structural_eq(&c));
+        assert!(a.structural_eq(&a.clone()));
+    }
+
+    #[test]
+    fn transform_wraps_every_paragraph() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.article([])([
+            b.p([])(b.text("One")),
+            b.section([])(b.p([])(b.text("Two"))),
+        ]);
+
+        let transformed = element.transform(&bump, |e| {
+            if e.tag() == Some("p") {
+                b.div([b.attr(("class", "para"))])(e)
+            } else {
+                e
+            }
+        });
+
+        let expected = b.article([])([
+            b.div([b.attr(("class", "para"))])(b.p([])(b.text("One"))),
+            b.section([])(b.div([b.attr(("class", "para"))])(b.p([])(b.text("Two")))),
+        ]);
+        assert_eq!(transformed, expected);
+    }
+
+    #[test]
+    fn flattened_hoists_nested_fragments() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.fragment([
+            b.fragment([b.p([])(b.text("One")), Element::Empty]),
+            b.div([])(b.fragment([b.fragment([b.text("Two")]), Element::Empty])),
+            b.fragment([b.fragment([b.p([])(b.text("Three"))])]),
+        ]);
+
+        let expected = Element::Fragment {
+            children: BumpVec::from_iter_in(
+                [
+                    b.p([])(b.text("One")),
+                    b.div([])(b.text("Two")),
+                    b.p([])(b.text("Three")),
+                ],
+                &bump,
+            ),
+        };
+        assert_eq!(element.flattened(&bump), expected);
+
+        let single = b.fragment([b.fragment([Element::Empty, b.span([])([])])]);
+        assert_eq!(single.flattened(&bump), b.span([])([]));
+        assert_eq!(
+            b.fragment([b.fragment([])]).flattened(&bump),
+            Element::Empty
+        );
+    }
+
+    #[test]
+    fn push_and_extend_children() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let mut list = b.ul([])(b.li([])(b.text("1")));
+        list.push_child(b.li([])(b.text("2"))).unwrap();
+        list.extend_children((3..=4).map(|i| b.li([])(b.text(&i.to_string()))))
+            .unwrap();
+        assert_eq!(
+            list,
+            b.ul([])([
+                b.li([])(b.text("1")),
+                b.li([])(b.text("2")),
+                b.li([])(b.text("3")),
+                b.li([])(b.text("4")),
+            ])
+        );
+
+        let mut input = b.input([]);
+        assert_eq!(
+            input.push_child(b.text("nope")),
+            Err(AddChildError::VoidElement {
+                name: "input".to_string()
+            })
+        );
+        assert_eq!(input, b.input([]));
+
+        let mut text = b.text("text");
+        assert_eq!(
+            text.extend_children([b.text("nope")]),
+            Err(AddChildError::NotAContainer)
+        );
+    }
+
+    #[test]
+    fn size_introspection() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([b.attr(("class", "box"))])([
+            b.p([])(b.text("Hello")),
+            b.raw("<hr>"),
+            b.fragment([b.span([])(b.text("a")), b.text("b")]),
+        ]);
+
+        // div, p, "Hello", raw, fragment, span, "a", "b"
+        assert_eq!(element.node_count(), 8);
+        // div > fragment > span > "a"
+        assert_eq!(element.depth(), 4);
+        // "div" + "class" + "box" + "p" + "Hello" + "<hr>" + "span" + "a" + "b"
+        assert_eq!(
+            element.byte_size_estimate(),
+            3 + 5 + 3 + 1 + 5 + 4 + 4 + 1 + 1
+        );
+
+        assert_eq!(Element::Empty.node_count(), 1);
+        assert_eq!(Element::Empty.depth(), 1);
+        assert_eq!(Element::Empty.byte_size_estimate(), 0);
+    }
+
+    #[test]
+    fn result_into_element() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let ok: Result<&str, std::num::ParseIntError> = Ok("x");
+        assert_eq!(b.p([])(ok).to_string(), "<p>x</p>");
+
+        let err = "nope".parse::<u32>().map(|n| n.to_string());
+        assert_eq!(
+            b.p([])(err).to_string(),
+            "<p>invalid digit found in string</p>"
+        );
+
+        let nested: Result<Option<&str>, std::fmt::Error> = Ok(None);
+        assert!(nested.into_element(&bump).is_empty());
+    }
+
+    #[test]
+    fn wrap_keeps_fragments_intact() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let section = b
+            .text("Hello")
+            .wrap(&bump, "section", [b.attr(("class", "intro"))]);
+        let Element::Tag { name, children, .. } = &section else {
+            panic!("expected tag");
+        };
+        assert_eq!(name.as_str(), "section");
+        assert_eq!(children.len(), 1);
+        assert!(children[0].is_text());
+        assert_eq!(
+            section.to_string(),
+            r#"<section class="intro">Hello</section>"#
+        );
+
+        let fragment = b.fragment([b.text("a"), b.text("b")]);
+        let Element::Tag { children, .. } = fragment.wrap(&bump, "div", []) else {
+            panic!("expected tag");
+        };
+        assert_eq!(children.len(), 1);
+        assert!(children[0].is_fragment());
+
+        let Element::Tag { children, .. } = Element::wrap_many(
+            &bump,
+            "ul",
+            [],
+            [b.li([])(b.text("1")), Element::Empty, b.li([])(b.text("2"))],
+        ) else {
+            panic!("expected tag");
+        };
+        assert_eq!(children.len(), 2);
+    }
+
+    #[cfg(feature = "parser")]
+    #[test]
+    fn raw_checked_rejects_malformed_html() {
+        let bump = Bump::new();
+
+        let html = "<div class=card><b>Hello</b>&amp; welcome</div>";
+        let element = Element::raw_checked(&bump, html).unwrap();
+        assert!(element.is_raw());
+        assert_eq!(element.to_string(), html);
+
+        assert!(Element::raw_checked(&bump, "<div><span>Hello</div>").is_err());
+        assert!(Element::raw_checked(&bump, "<div>Hello").is_err());
+    }
+
+    #[test]
+    fn get_set_remove_and_toggle_attributes() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let mut button = b.tag(
+            "button",
+            [b.attr(("class", "primary")), b.attr(("type", "submit"))],
+            false,
+        )([]);
+
+        assert_eq!(
+            button.attr("class").and_then(|a| a.value_as_str()),
+            Some("primary")
+        );
+        // Lookups are exact, while modifications match keys case-insensitively
+        assert!(button.attr("CLASS").is_none());
+        let old = button.set_attr(b.attr(("class", "secondary"))).unwrap();
+        assert_eq!(old.value_as_str(), Some("primary"));
+        assert!(button.set_attr(b.attr(("id", "go"))).is_none());
+
+        let removed = button.remove_attr("TYPE").unwrap();
+        assert_eq!(removed.value_as_str(), Some("submit"));
+        assert!(button.remove_attr("type").is_none());
+
+        button.toggle_attr(&bump, "disabled");
+        assert_eq!(
+            button.to_string(),
+            r#"<button class="secondary" id="go" disabled></button>"#
+        );
+        button.toggle_attr(&bump, "disabled");
+        assert_eq!(
+            button.to_string(),
+            r#"<button class="secondary" id="go"></button>"#
+        );
+
+        let mut text = b.text("x");
+        text.toggle_attr(&bump, "disabled");
+        assert!(text.set_attr(b.attr("hidden")).is_none());
+        assert!(text.remove_attr("disabled").is_none());
+        assert_eq!(text.to_string(), "x");
+    }
+
+    #[test]
+    fn children_across_variants() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+
+        let list = b.ul([])([b.li([])(b.text("a")), b.li([])(b.text("b"))]);
+        assert_eq!(list.children().len(), 2);
+        assert_eq!(list.children()[0].children().len(), 1);
+        assert_eq!(
+            b.fragment([b.text("a"), b.text("b"), b.hr([])])
+                .children()
+                .len(),
+            3
+        );
+        assert_eq!(b.br([]).children().len(), 0);
+        assert_eq!(b.text("a").children().len(), 0);
+        assert_eq!(b.raw("<hr>").children().len(), 0);
+        assert_eq!(Element::Empty.children().len(), 0);
+
+        let children = list.into_children(&bump);
+        assert_eq!(children.len(), 2);
+        assert!(children.iter().all(|c| c.is_tag()));
+        assert!(b.text("a").into_children(&bump).is_empty());
+    }
+
+    #[test]
+    fn replace_subtree_splices_fragments() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let slot = |name| b.tag("slot", [b.attr(("name", name))], false)([]);
+        let template = b.article([])([b.h1([])(b.text("Title")), slot("content"), slot("footer")]);
+
+        let is_slot = |element: &Element, slot_name: &str| {
+            matches!(element, Element::Tag { name, .. } if name == "slot")
+                && element.attr("name").and_then(|a| a.value_as_str()) == Some(slot_name)
+        };
+        let result = template.replace_subtree(&bump, |element| {
+            if is_slot(element, "content") {
+                Some(b.fragment([b.p([])(b.text("One")), b.p([])(b.text("Two"))]))
+            } else if is_slot(element, "footer") {
+                Some(Element::Empty)
+            } else {
+                None
+            }
+        });
+
+        assert_eq!(result.children().len(), 3);
+        assert_eq!(
+            result.to_string(),
+            "<article>\n  <h1>Title</h1>\n  <p>One</p>\n  <p>Two</p>\n</article>"
+        );
+    }
+
+    #[test]
+    fn replace_subtree_only_splices_and_removes_replacements() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        fn fragment<'b>(bump: &'b Bump, children: Vec<Element<'b>>) -> Element<'b> {
+            Element::Fragment {
+                children: BumpVec::from_iter_in(children, bump),
+            }
+        }
+        let old = fragment(&bump, vec![b.span([])(b.text("Old"))]);
+        let tree = fragment(
+            &bump,
+            vec![old.clone(), Element::Empty, b.p([])(b.text("Kept"))],
+        );
+
+        let result = tree.replace_subtree(&bump, |element| {
+            (*element == old).then(|| {
+                fragment(
+                    &bump,
+                    vec![b.em([])(b.text("One")), b.em([])(b.text("Two"))],
+                )
+            })
+        });
+
+        // The replacement fragment is spliced, while the untouched `Empty` is kept
+        let children = result.children();
+        assert_eq!(children.len(), 4);
+        assert!(matches!(&children[0], Element::Tag { name, .. } if name == "em"));
+        assert!(matches!(&children[1], Element::Tag { name, .. } if name == "em"));
+        assert!(matches!(children[2], Element::Empty));
+        assert!(matches!(&children[3], Element::Tag { name, .. } if name == "p"));
+    }
+
+    #[test]
+    fn render_to_string_renders_standalone_element() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([b.attr(("class", "card"))])(b.p([])(b.text("Hello")));
+
+        assert_eq!(
+            element.render_to_string(&bump).unwrap(),
+            "<div class=\"card\">\n  <p>Hello</p>\n</div>"
+        );
+        assert_eq!(
+            element.render_to_string(&bump).unwrap(),
+            element.to_string()
+        );
+
+        // Rendering with a scratch allocator leaves the element's allocator untouched
+        let allocated = bump.allocated_bytes();
+        let scratch = Bump::new();
+        assert_eq!(
+            element.render_to_string(&scratch).unwrap(),
+            element.to_string()
+        );
+        assert_eq!(bump.allocated_bytes(), allocated);
+    }
+
+    #[test]
+    // The bump reference has interior mutability, but is not hashed
+    #[allow(clippy::mutable_key_type)]
+    fn equal_trees_hash_equally() {
+        use std::collections::HashSet;
+
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let card = |title| {
+            b.div([
+                b.attr(("class", "card")),
+                Attribute::new_float(&bump, "ratio", 1.5),
+            ])(b.h2([])(b.text(title)))
+        };
+
+        let mut set = HashSet::new();
+        assert!(set.insert(card("One")));
+        assert!(!set.insert(card("One")));
+        assert!(set.insert(card("Two")));
+        assert_eq!(set.len(), 2);
+
+        // Attribute order participates, as with `PartialEq`
+        let reordered = b.div([
+            Attribute::new_float(&bump, "ratio", 1.5),
+            b.attr(("class", "card")),
+        ])(b.h2([])(b.text("One")));
+        assert!(set.insert(reordered));
+
+        // Floats are compared by their rendered representation
+        assert_ne!(
+            Attribute::new_float(&bump, "x", 0.0),
+            Attribute::new_float(&bump, "x", -0.0)
+        );
+        assert_eq!(
+            Attribute::new_float(&bump, "x", f64::NAN),
+            Attribute::new_float(&bump, "x", f64::NAN)
+        );
+
+        let render = |element| RenderElement::from_elements(&bump, [element]);
+        let render_set: HashSet<_> = [render(card("One")), render(card("One"))]
+            .into_iter()
+            .collect();
+        assert_eq!(render_set.len(), 1);
+    }
+
+    #[test]
+    fn inner_text_separates_block_elements() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let article = b.article([])([
+            b.h1([])(b.text("Title")),
+            b.p([])([b.text("First "), b.em([])(b.text("para")), b.text("graph")]),
+            b.p([])(b.text("Second")),
+            b.raw("<hr>"),
+        ]);
+
+        assert_eq!(article.inner_text_owned(), "TitleFirst paragraphSecond");
+        assert_eq!(
+            article.inner_text_owned(),
+            article.inner_text(&bump).as_str()
+        );
+        assert_eq!(
+            article.inner_text_with_block_separator(" "),
+            "Title First paragraph Second"
+        );
+        assert_eq!(
+            article.inner_text_with_block_separator("\n\n"),
+            "Title\n\nFirst paragraph\n\nSecond"
+        );
+        assert_eq!(
+            b.span([])(b.text("x")).inner_text_with_block_separator(" "),
+            "x"
+        );
+    }
+}
@@ -2,7 +2,7 @@ use bumpalo::collections::String as BumpString;
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 
-use crate::{Attribute, AttributeValue, Element};
+use crate::{Attribute, AttributeValue, Element, SanitizePolicy};
 use paxhtml_parser::{AstAttribute, AstNode, AttributeValue as AstAttributeValue, ParseError};
 use std::fmt;
 
@@ -13,6 +13,16 @@ pub enum EvalError {
     InterpolationNotSupported,
     /// Expression attributes are not supported at runtime
     ExpressionAttributeNotSupported,
+    /// A tag is not allowed by the [`SanitizePolicy`](crate::SanitizePolicy) in use
+    DisallowedTag {
+        /// The name of the disallowed tag
+        name: String,
+    },
+    /// An attribute is not allowed by the [`SanitizePolicy`](crate::SanitizePolicy) in use
+    DisallowedAttribute {
+        /// The name of the disallowed attribute
+        name: String,
+    },
 }
 impl fmt::Display for EvalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -29,6 +39,12 @@ impl fmt::Display for EvalError {
                     "Expression attributes are not supported in runtime HTML evaluation"
                 )
             }
+            EvalError::DisallowedTag { name } => {
+                write!(f, "The tag `<{name}>` is not allowed")
+            }
+            EvalError::DisallowedAttribute { name } => {
+                write!(f, "The attribute `{name}` is not allowed")
+            }
         }
     }
 }
@@ -97,6 +113,84 @@ pub fn parse_html<'bump>(bump: &'bump Bump, html: &str) -> Result<Element<'bump>
     Ok(element)
 }
 
+/// Parse an HTML string containing any number of top-level nodes into a runtime
+/// [Element::Fragment].
+///
+/// This accepts the same syntax as [parse_html]; see [paxhtml_parser::parse_html_fragment].
+///
+/// # Errors
+///
+/// Returns a [ParseHtmlError] if the HTML is malformed or contains features not
+/// supported at runtime (like interpolation syntax).
+pub fn parse_html_fragment<'bump>(
+    bump: &'bump Bump,
+    html: &str,
+) -> Result<Element<'bump>, ParseHtmlError> {
+    let ast = paxhtml_parser::parse_html_fragment(html)?;
+    let element = eval_node(bump, &ast)?;
+    Ok(element)
+}
+
+/// Parse a plain HTML string into a runtime [Element] tree.
+///
+/// Unlike [parse_html], this accepts standard HTML, where text content is not quoted.
+/// See [paxhtml_parser::parse_plain_html] for details on how the input is interpreted.
+///
+/// # Example
+///
+/// ```
+/// use paxhtml::{bumpalo::Bump, builder::Builder, parse_plain_html};
+///
+/// let bump = Bump::new();
+/// let b = Builder::new(&bump);
+/// let element = parse_plain_html(&bump, r#"<div class="container"><p>Hello, world!</p></div>"#).unwrap();
+/// let doc = b.document([element]);
+/// let html = doc.write_to_string();
+/// ```
+///
+/// # Errors
+///
+/// Returns a [ParseHtmlError] if the HTML is malformed.
+pub fn parse_plain_html<'bump>(
+    bump: &'bump Bump,
+    html: &str,
+) -> Result<Element<'bump>, ParseHtmlError> {
+    let ast = paxhtml_parser::parse_plain_html(html)?;
+    let element = eval_node(bump, &ast)?;
+    Ok(element)
+}
+
+/// Parse a plain HTML string into a runtime [Element] tree, recovering from malformed
+/// markup instead of failing.
+///
+/// This is intended for untrusted input, such as user-supplied comments. Unclosed and
+/// mismatched tags are closed, and stray `<`s are treated as text; see
+/// [paxhtml_parser::parse_plain_html_lenient] for details. Every error that was recovered
+/// from is returned alongside the tree.
+///
+/// # Example
+///
+/// ```
+/// use paxhtml::{bumpalo::Bump, parse_plain_html_lenient};
+///
+/// let bump = Bump::new();
+/// let (element, errors) = parse_plain_html_lenient(&bump, "<p>1 < 2");
+/// assert_eq!(element.inner_text(&bump), "1 < 2");
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn parse_plain_html_lenient<'bump>(
+    bump: &'bump Bump,
+    html: &str,
+) -> (Element<'bump>, Vec<ParseHtmlError>) {
+    let (ast, errors) = paxhtml_parser::parse_plain_html_lenient(html);
+    let mut errors: Vec<ParseHtmlError> = errors.into_iter().map(Into::into).collect();
+    let element = eval_node(bump, &ast).unwrap_or_else(|e| {
+        errors.push(e.into());
+        Element::Empty
+    });
+    (element, errors)
+}
+
 /// Convert an AST node to a runtime Element
 pub fn eval_node<'bump>(bump: &'bump Bump, node: &AstNode) -> Result<Element<'bump>, EvalError> {
     match node {
@@ -108,7 +202,7 @@ pub fn eval_node<'bump>(bump: &'bump Bump, node: &AstNode) -> Result<Element<'bu
         } => {
             let mut attrs = BumpVec::new_in(bump);
             for attr in attributes {
-                attrs.push(eval_attribute(bump, attr)?);
+                attrs.extend(eval_attribute(bump, attr, None)?);
             }
 
             let mut child_elements = BumpVec::new_in(bump);
@@ -120,7 +214,8 @@ pub fn eval_node<'bump>(bump: &'bump Bump, node: &AstNode) -> Result<Element<'bu
                 name: BumpString::from_str_in(name, bump),
                 attributes: attrs,
                 children: child_elements,
-                void: *void,
+                void: *void || crate::builder::is_void_tag(name),
+                key: None,
             })
         }
         AstNode::Fragment(children) => {
@@ -133,18 +228,27 @@ pub fn eval_node<'bump>(bump: &'bump Bump, node: &AstNode) -> Result<Element<'bu
                 children: child_elements,
             })
         }
-        AstNode::Expression { .. } => Err(EvalError::InterpolationNotSupported),
+        AstNode::DynamicElement { .. } | AstNode::Expression { .. } | AstNode::For { .. } => {
+            Err(EvalError::InterpolationNotSupported)
+        }
         AstNode::Text(text) => Ok(Element::Text {
             text: BumpString::from_str_in(text, bump),
         }),
+        AstNode::Raw(html) => Ok(Element::Raw {
+            html: BumpString::from_str_in(html, bump),
+        }),
     }
 }
 
 /// Convert an AST attribute to a runtime Attribute
-fn eval_attribute<'bump>(
+///
+/// If a sanitize policy is given, attributes it does not allow are dropped (returning `None`)
+/// or reported as errors, depending on the policy.
+pub(crate) fn eval_attribute<'bump>(
     bump: &'bump Bump,
     attr: &AstAttribute,
-) -> Result<Attribute<'bump>, EvalError> {
+    policy: Option<&SanitizePolicy>,
+) -> Result<Option<Attribute<'bump>>, EvalError> {
     match attr {
         AstAttribute::Named { name, value } => {
             let val = match value {
@@ -160,12 +264,24 @@ fn eval_attribute<'bump>(
                 }
             };
 
-            Ok(Attribute {
+            let attribute = Attribute {
                 key: BumpString::from_str_in(name, bump),
                 value: val,
-            })
+            };
+            match policy {
+                Some(policy) if !policy.allows_attribute(&attribute) => {
+                    if policy.error_on_disallowed {
+                        Err(EvalError::DisallowedAttribute { name: name.clone() })
+                    } else {
+                        Ok(None)
+                    }
+                }
+                _ => Ok(Some(attribute)),
+            }
+        }
+        AstAttribute::Interpolated(_) | AstAttribute::Spread(_) => {
+            Err(EvalError::InterpolationNotSupported)
         }
-        AstAttribute::Interpolated(_) => Err(EvalError::InterpolationNotSupported),
     }
 }
 
@@ -187,6 +303,7 @@ mod tests {
                 attributes,
                 children,
                 void,
+                ..
             } => {
                 assert_eq!(name.as_str(), "div");
                 assert!(!void);
@@ -265,4 +382,22 @@ mod tests {
             _ => panic!("Expected tag element"),
         }
     }
+
+    #[test]
+    fn test_eval_typed_literal_attributes() {
+        let bump = Bump::new();
+        let html = r#"<input tabindex=0 hidden=true step=0.5 />"#;
+        let ast = parse_html(html).unwrap();
+        let element = eval_node(&bump, &ast).unwrap();
+
+        match element {
+            Element::Tag { attributes, .. } => {
+                assert_eq!(attributes.len(), 3);
+                assert_eq!(attributes[0].value, Some(AttributeValue::Int(0)));
+                assert_eq!(attributes[1].value, Some(AttributeValue::Bool(true)));
+                assert_eq!(attributes[2].value, Some(AttributeValue::Float(0.5)));
+            }
+            _ => panic!("Expected tag element"),
+        }
+    }
 }
@@ -1,4 +1,5 @@
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 //! A crate for generating formatted HTML from a runtime-generated tree of elements.
 //!
 //! Elements are created through either [builder] or [html], and placed in a [Document],
@@ -23,6 +24,16 @@
 //! let doc = b.document([element]);
 //! let html_string = doc.write_to_string().unwrap();
 //! ```
+//!
+//! # Features
+//!
+//! The `std` feature, enabled by default, provides rendering to [`std::io::Write`], writing
+//! documents to files with [`RoutePath`], and the `macros`, `parser`, `serde` and `http`
+//! features, all of which require it. Without it, the crate only requires `alloc`: trees
+//! can be built and rendered to a [`core::fmt::Write`] with methods such as
+//! [`Document::format_with_options`].
+
+extern crate alloc;
 
 pub mod builder;
 pub mod util;
@@ -30,29 +41,57 @@ pub mod util;
 // Re-export bumpalo for convenience
 pub use bumpalo;
 
+mod arena;
+pub use arena::Arena;
+
 mod attribute;
-pub use attribute::{Attribute, AttributeParseError, AttributeValue, IntoAttribute};
+pub use attribute::{
+    Attribute, AttributeParseError, AttributeValue, IntoAttribute, IntoAttributeValue,
+    InvalidAttributeName,
+};
 
 mod document;
-pub use document::Document;
+pub use document::{Document, PageOptions, RenderStats};
 
 mod element;
-pub use element::{DefaultIn, Element, IntoElement};
+pub use element::{AddChildError, DefaultIn, Element, FromChildren, IntoElement};
 
 #[cfg(feature = "parser")]
 mod eval;
 #[cfg(feature = "parser")]
-pub use eval::{eval_node, parse_html, EvalError, ParseHtmlError};
+pub use eval::{
+    eval_node, parse_html, parse_html_fragment, parse_plain_html, parse_plain_html_lenient,
+    EvalError, ParseHtmlError,
+};
+
+mod page;
+pub use page::Page;
+
+#[cfg(feature = "parser")]
+mod sanitize;
+#[cfg(feature = "parser")]
+pub use sanitize::{eval_node_sanitized, SanitizePolicy};
 
 mod render_element;
-pub use render_element::RenderElement;
+pub use render_element::{
+    AttributeQuote, BooleanAttrStyle, CollapseWhitespace, RenderElement, RenderOptions,
+};
+
+mod selector;
+pub use selector::SelectorError;
 
+#[cfg(feature = "std")]
 mod routing;
-pub use routing::RoutePath;
+#[cfg(feature = "std")]
+pub use routing::{sitemap, RoutePath, TrailingSlash};
 
 #[cfg(feature = "macros")]
-pub use paxhtml_macro::html;
+pub use paxhtml_macro::{attr, html, DefaultIn};
 
 // Re-export parser types for convenience
 #[cfg(feature = "parser")]
-pub use paxhtml_parser::{parse_html as parse_html_ast, AstNode, ParseError};
+pub use paxhtml_parser::{
+    parse_html as parse_html_ast, parse_html_fragment as parse_html_fragment_ast,
+    parse_plain_html as parse_plain_html_ast,
+    parse_plain_html_lenient as parse_plain_html_lenient_ast, AstNode, ParseError,
+};
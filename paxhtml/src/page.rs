@@ -0,0 +1,117 @@
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::{builder::Builder, Document, Element, PageOptions};
+
+/// A builder for a complete HTML page, assembled from separately-provided `<head>` and
+/// `<body>` elements.
+///
+/// Head elements are deduplicated when the page is built: only the last `<title>` is kept,
+/// and `<meta charset>` elements are dropped, as [`Document::new_page_with_options`] always
+/// inserts one.
+///
+/// ```
+/// use paxhtml::{bumpalo::Bump, builder::Builder, Page};
+///
+/// let bump = Bump::new();
+/// let b = Builder::new(&bump);
+/// let document = Page::new(&bump)
+///     .title("Home")
+///     .head([b.link([b.attr(("rel", "stylesheet")), b.attr(("href", "/style.css"))])])
+///     .body([b.h1([])(b.text("Hello"))])
+///     .build();
+/// ```
+pub struct Page<'bump> {
+    bump: &'bump Bump,
+    options: PageOptions,
+    head: BumpVec<'bump, Element<'bump>>,
+    body: BumpVec<'bump, Element<'bump>>,
+}
+impl<'bump> Page<'bump> {
+    /// Create an empty page with the default [`PageOptions`].
+    pub fn new(bump: &'bump Bump) -> Self {
+        Self {
+            bump,
+            options: PageOptions::default(),
+            head: BumpVec::new_in(bump),
+            body: BumpVec::new_in(bump),
+        }
+    }
+
+    /// Set the [`PageOptions`] used to build the page.
+    pub fn options(mut self, options: PageOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the `<title>` of the page, replacing any previously-provided title.
+    pub fn title(self, title: &str) -> Self {
+        let b = Builder::new(self.bump);
+        self.head([b.title([])(b.text(title))])
+    }
+
+    /// Append elements to the `<head>` of the page.
+    pub fn head(mut self, elements: impl IntoIterator<Item = Element<'bump>>) -> Self {
+        self.head.extend(elements);
+        self
+    }
+
+    /// Append elements to the `<body>` of the page.
+    pub fn body(mut self, elements: impl IntoIterator<Item = Element<'bump>>) -> Self {
+        self.body.extend(elements);
+        self
+    }
+
+    /// Build the page into a [`Document`] using [`Document::new_page_with_options`].
+    pub fn build(self) -> Document<'bump> {
+        let last_title = self.head.iter().rposition(is_title);
+        let head = self
+            .head
+            .into_iter()
+            .enumerate()
+            .filter(|(i, element)| {
+                !is_charset_meta(element) && (!is_title(element) || Some(*i) == last_title)
+            })
+            .map(|(_, element)| element);
+        Document::new_page_with_options(self.bump, &self.options, head, self.body)
+    }
+}
+
+fn is_title(element: &Element) -> bool {
+    matches!(element, Element::Tag { name, .. } if name.eq_ignore_ascii_case("title"))
+}
+
+fn is_charset_meta(element: &Element) -> bool {
+    matches!(
+        element,
+        Element::Tag { name, attributes, .. }
+            if name.eq_ignore_ascii_case("meta")
+                && attributes.iter().any(|a| a.key.eq_ignore_ascii_case("charset"))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deduplicates_title_and_charset() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let document = Page::new(&bump)
+            .options(PageOptions { viewport: None })
+            .title("First")
+            .head([b.meta([b.attr(("charset", "latin1"))])])
+            .title("Second")
+            .body([b.p([])(b.text("Hi"))])
+            .build();
+
+        assert_eq!(
+            document.write_compact_to_string().unwrap(),
+            concat!(
+                "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Second</title></head>",
+                "<body><p>Hi</p></body></html>"
+            )
+        );
+    }
+}
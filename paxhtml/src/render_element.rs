@@ -1,12 +1,303 @@
-use std::io::Write;
-
 use bumpalo::collections::String as BumpString;
 use bumpalo::collections::Vec as BumpVec;
 use bumpalo::Bump;
 
+use alloc::{string::String, vec, vec::Vec};
+
 use crate::{Attribute, AttributeValue, Element};
 
-#[derive(Debug, Clone, PartialEq)]
+/// An error produced while rendering.
+///
+/// With the `std` feature, this is an [`std::io::Error`], so that errors from the underlying
+/// writer are kept. Otherwise, it is a [`core::fmt::Error`].
+#[cfg(feature = "std")]
+pub(crate) type RenderError = std::io::Error;
+#[cfg(not(feature = "std"))]
+pub(crate) type RenderError = core::fmt::Error;
+
+pub(crate) type RenderResult<T> = Result<T, RenderError>;
+
+#[cfg(feature = "std")]
+fn formatter_error() -> RenderError {
+    std::io::Error::other("formatter error")
+}
+#[cfg(not(feature = "std"))]
+fn formatter_error() -> RenderError {
+    core::fmt::Error
+}
+
+#[cfg(feature = "std")]
+fn void_with_children_error(element: &RenderElement) -> RenderError {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("Void element ({element:?}) has children"),
+    )
+}
+#[cfg(not(feature = "std"))]
+fn void_with_children_error(_element: &RenderElement) -> RenderError {
+    core::fmt::Error
+}
+
+/// A destination for rendered HTML.
+///
+/// This abstracts over [`std::io::Write`] and [`core::fmt::Write`], so that both can be
+/// rendered to directly without converting between bytes and strings.
+pub(crate) trait WriteSink {
+    fn write_str(&mut self, s: &str) -> RenderResult<()>;
+
+    /// The column the next write starts at, if tracked.
+    fn column(&self) -> Option<usize> {
+        None
+    }
+
+    fn write_fmt(&mut self, args: core::fmt::Arguments<'_>) -> RenderResult<()> {
+        if let Some(s) = args.as_str() {
+            return self.write_str(s);
+        }
+
+        // Keep the underlying error, as `core::fmt::Error` carries no information
+        struct Adapter<'a, S: WriteSink + ?Sized> {
+            sink: &'a mut S,
+            error: Option<RenderError>,
+        }
+        impl<S: WriteSink + ?Sized> core::fmt::Write for Adapter<'_, S> {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                self.sink.write_str(s).map_err(|e| {
+                    self.error = Some(e);
+                    core::fmt::Error
+                })
+            }
+        }
+
+        let mut adapter = Adapter {
+            sink: self,
+            error: None,
+        };
+        core::fmt::write(&mut adapter, args)
+            .map_err(|_| adapter.error.take().unwrap_or_else(formatter_error))
+    }
+}
+
+/// Writes to a [`std::io::Write`].
+#[cfg(feature = "std")]
+pub(crate) struct IoSink<'a>(pub &'a mut dyn std::io::Write);
+#[cfg(feature = "std")]
+impl WriteSink for IoSink<'_> {
+    fn write_str(&mut self, s: &str) -> RenderResult<()> {
+        self.0.write_all(s.as_bytes())
+    }
+}
+
+/// Writes to a [`core::fmt::Write`].
+pub(crate) struct FmtSink<'a>(pub &'a mut dyn core::fmt::Write);
+impl WriteSink for FmtSink<'_> {
+    fn write_str(&mut self, s: &str) -> RenderResult<()> {
+        self.0.write_str(s).map_err(|_| formatter_error())
+    }
+}
+
+/// Passes each write to a callback as a string chunk.
+#[cfg(feature = "std")]
+pub(crate) struct ChunkSink<F: FnMut(&str) -> std::io::Result<()>>(pub F);
+#[cfg(feature = "std")]
+impl<F: FnMut(&str) -> std::io::Result<()>> WriteSink for ChunkSink<F> {
+    fn write_str(&mut self, s: &str) -> RenderResult<()> {
+        (self.0)(s)
+    }
+}
+
+/// Tracks the current column of the output written to another sink.
+struct ColumnSink<'a> {
+    inner: &'a mut dyn WriteSink,
+    column: usize,
+}
+impl WriteSink for ColumnSink<'_> {
+    fn write_str(&mut self, s: &str) -> RenderResult<()> {
+        match s.rfind('\n') {
+            Some(idx) => self.column = s[idx + 1..].chars().count(),
+            None => self.column += s.chars().count(),
+        }
+        self.inner.write_str(s)
+    }
+
+    fn column(&self) -> Option<usize> {
+        Some(self.column)
+    }
+}
+
+/// Calls `f` with `sink`, wrapped to track columns if the options require it.
+fn with_column_tracking<R>(
+    sink: &mut dyn WriteSink,
+    options: &RenderOptions,
+    f: impl FnOnce(&mut dyn WriteSink) -> RenderResult<R>,
+) -> RenderResult<R> {
+    if options.max_inline_width.is_some() {
+        f(&mut ColumnSink {
+            inner: sink,
+            column: 0,
+        })
+    } else {
+        f(sink)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Options controlling how [`RenderElement`]s are written.
+pub struct RenderOptions {
+    /// Whether block-level elements should be placed on their own indented lines.
+    ///
+    /// If `false`, no whitespace is inserted between elements.
+    pub pretty: bool,
+    /// How text nodes consisting only of whitespace are handled.
+    pub collapse_whitespace: CollapseWhitespace,
+    /// The quote character placed around attribute values.
+    pub attr_quote: AttributeQuote,
+    /// How attributes without a value are written. Declarations such as
+    /// `<!DOCTYPE html>` are always written bare.
+    pub boolean_attr_style: BooleanAttrStyle,
+    /// If set, tags with more than this many attributes have each attribute written on its
+    /// own indented line, followed by the end of the start tag on its own line. Has no
+    /// effect unless [`Self::pretty`] is set.
+    pub multiline_attrs_threshold: Option<usize>,
+    /// Whether attributes are written in [`Attribute::sort_order`] rather than the order
+    /// in which they were added.
+    pub sort_attrs: bool,
+    /// Whether void elements are self-closed (`<br/>`) rather than written as a lone start
+    /// tag (`<br>`). Declarations such as `<!DOCTYPE html>` are never self-closed.
+    pub self_close_void: bool,
+    /// Whether elements are self-closed if and only if they have no children, as in XML,
+    /// rather than based on whether they are void.
+    ///
+    /// When set, `<description></description>` is written as `<description/>`, and a void
+    /// element with children (such as an RSS `<link>`) is written with an end tag.
+    pub self_close_empty: bool,
+    /// The width at which a block-level element's line of inline content is wrapped, if any.
+    ///
+    /// When the start tag, inline children and end tag would not fit on one line, the
+    /// children are placed on indented lines and filled up to this width, breaking only
+    /// where the content already has whitespace. Has no effect unless [`Self::pretty`] is
+    /// set. Columns are counted from the start of the output.
+    pub max_inline_width: Option<usize>,
+}
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::pretty()
+    }
+}
+impl RenderOptions {
+    /// Options for indented output. This is the default.
+    pub fn pretty() -> Self {
+        Self {
+            pretty: true,
+            collapse_whitespace: CollapseWhitespace::default(),
+            attr_quote: AttributeQuote::default(),
+            boolean_attr_style: BooleanAttrStyle::default(),
+            multiline_attrs_threshold: None,
+            sort_attrs: false,
+            self_close_void: false,
+            self_close_empty: false,
+            max_inline_width: None,
+        }
+    }
+
+    /// Options for output with no inter-element whitespace.
+    pub fn compact() -> Self {
+        Self {
+            pretty: false,
+            collapse_whitespace: CollapseWhitespace::default(),
+            attr_quote: AttributeQuote::default(),
+            boolean_attr_style: BooleanAttrStyle::default(),
+            multiline_attrs_threshold: None,
+            sort_attrs: false,
+            self_close_void: false,
+            self_close_empty: false,
+            max_inline_width: None,
+        }
+    }
+
+    /// Options for writing to standard output: [`Self::pretty`] if stdout is a terminal,
+    /// and [`Self::compact`] otherwise (e.g. when piped to a file or another program).
+    #[cfg(feature = "std")]
+    pub fn for_stdout() -> Self {
+        use std::io::IsTerminal;
+        Self::for_terminal(std::io::stdout().is_terminal())
+    }
+
+    /// [`Self::pretty`] if `is_terminal` is `true`, and [`Self::compact`] otherwise.
+    ///
+    /// See [`Self::for_stdout`].
+    pub fn for_terminal(is_terminal: bool) -> Self {
+        if is_terminal {
+            Self::pretty()
+        } else {
+            Self::compact()
+        }
+    }
+
+    /// Options for XML output, such as documents created with
+    /// [`Document::new_xml`](crate::Document::new_xml).
+    ///
+    /// This is [`Self::compact`] with elements self-closed if and only if they are empty
+    /// (see [`Self::self_close_empty`]), and boolean attributes given their name as a value,
+    /// as XML requires every attribute to have a value.
+    pub fn xml() -> Self {
+        Self {
+            self_close_void: true,
+            self_close_empty: true,
+            boolean_attr_style: BooleanAttrStyle::Repeated,
+            ..Self::compact()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// The quote character used around attribute values.
+///
+/// Only the active quote character is escaped within values.
+pub enum AttributeQuote {
+    /// `key="value"`. This is the default.
+    #[default]
+    Double,
+    /// `key='value'`.
+    Single,
+}
+impl AttributeQuote {
+    /// The quote character.
+    pub fn as_char(self) -> char {
+        match self {
+            AttributeQuote::Double => '"',
+            AttributeQuote::Single => '\'',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// How boolean attributes (attributes without a value) are written.
+pub enum BooleanAttrStyle {
+    /// `disabled`. This is the default.
+    #[default]
+    Bare,
+    /// `disabled=""`.
+    Empty,
+    /// `disabled="disabled"`, as required by XHTML.
+    Repeated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// How text nodes consisting only of whitespace (including empty text) are rendered.
+pub enum CollapseWhitespace {
+    /// Whitespace-only text nodes are written like any other text. This is the default.
+    ///
+    /// Note that any text node, including a whitespace-only one, stops the block-level
+    /// siblings that follow it from being indented.
+    #[default]
+    Keep,
+    /// Whitespace-only text nodes are omitted, except within
+    /// [whitespace-preserving](RenderElement::preserves_whitespace) elements.
+    Remove,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
 /// A renderable element in an HTML document.
@@ -24,6 +315,9 @@ pub enum RenderElement<'bump> {
         children: BumpVec<'bump, RenderElement<'bump>>,
         /// Whether the tag is void.
         void: bool,
+        /// The [key](Element::key) of the tag, if any. This is not rendered.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        key: Option<BumpString<'bump>>,
     },
     /// A text element.
     Text {
@@ -31,11 +325,20 @@ pub enum RenderElement<'bump> {
         text: BumpString<'bump>,
     },
     /// A raw element.
+    ///
+    /// Raw HTML is written verbatim. When rendering with indentation, it is placed on its
+    /// own line if it is among block-level siblings, and written inline otherwise.
     Raw {
         /// The raw HTML of the element.
         html: BumpString<'bump>,
     },
 }
+impl core::fmt::Display for RenderElement<'_> {
+    /// Renders the element with [`RenderOptions::pretty`].
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.format_with_options(f, 0, &RenderOptions::default())
+    }
+}
 impl<'bump> RenderElement<'bump> {
     /// Convert a list of [`Element`]s into a list of [`RenderElement`]s.
     ///
@@ -53,18 +356,19 @@ impl<'bump> RenderElement<'bump> {
                     attributes,
                     children,
                     void,
+                    key,
                 } => {
                     result.push(Self::Tag {
                         name,
                         attributes,
                         children: Self::from_elements(bump, children),
                         void,
+                        key,
                     });
                 }
                 Element::Fragment { children } => {
                     result.extend(Self::from_elements(bump, children));
                 }
-                Element::Text { text } if text.as_str() == "\n" => {}
                 Element::Text { text } => {
                     result.push(Self::Text { text });
                 }
@@ -76,56 +380,285 @@ impl<'bump> RenderElement<'bump> {
         result
     }
 
+    /// Convert a borrowed [`Element`] into a list of [`RenderElement`]s, copying its contents
+    /// into `bump`.
+    ///
+    /// Unlike [`Self::from_elements`], this leaves the element untouched and allocates
+    /// nothing in its bump allocator, so a short-lived scratch allocator can be used to
+    /// render it. Fragments are flattened, as with [`Self::from_elements`].
+    pub fn from_element_ref(bump: &'bump Bump, element: &Element) -> BumpVec<'bump, Self> {
+        fn convert<'bump>(
+            bump: &'bump Bump,
+            element: &Element,
+            result: &mut BumpVec<'bump, RenderElement<'bump>>,
+        ) {
+            match element {
+                Element::Empty => {}
+                Element::Tag {
+                    name,
+                    attributes,
+                    children,
+                    void,
+                    key,
+                } => {
+                    let mut render_children = BumpVec::new_in(bump);
+                    for child in children {
+                        convert(bump, child, &mut render_children);
+                    }
+                    result.push(RenderElement::Tag {
+                        name: BumpString::from_str_in(name, bump),
+                        attributes: BumpVec::from_iter_in(
+                            attributes.iter().map(|attr| attr.clone_in(bump)),
+                            bump,
+                        ),
+                        children: render_children,
+                        void: *void,
+                        key: key.as_ref().map(|key| BumpString::from_str_in(key, bump)),
+                    });
+                }
+                Element::Fragment { children } => {
+                    for child in children {
+                        convert(bump, child, result);
+                    }
+                }
+                Element::Text { text } => result.push(RenderElement::Text {
+                    text: BumpString::from_str_in(text, bump),
+                }),
+                Element::Raw { html } => result.push(RenderElement::Raw {
+                    html: BumpString::from_str_in(html, bump),
+                }),
+            }
+        }
+
+        let mut result = BumpVec::new_in(bump);
+        convert(bump, element, &mut result);
+        result
+    }
+
     /// Write the element to a string.
+    #[cfg(feature = "std")]
     pub fn write_to_string(&self) -> std::io::Result<String> {
-        let mut output = vec![];
-        self.write(&mut output, 0)?;
-        Ok(String::from_utf8(output).unwrap())
+        let mut output = String::new();
+        self.write_fmt(&mut output, 0)?;
+        Ok(output)
     }
 
     /// Write the element to a writer.
-    pub fn write(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    #[cfg(feature = "std")]
+    pub fn write(&self, writer: &mut dyn std::io::Write, depth: usize) -> std::io::Result<()> {
+        self.write_with_options(writer, depth, &RenderOptions::default())
+    }
+
+    /// Write the element to a writer using the given [`RenderOptions`].
+    #[cfg(feature = "std")]
+    pub fn write_with_options(
+        &self,
+        writer: &mut dyn std::io::Write,
+        depth: usize,
+        options: &RenderOptions,
+    ) -> std::io::Result<()> {
+        with_column_tracking(&mut IoSink(writer), options, |sink| {
+            self.write_inner(sink, depth, options, false)
+        })
+    }
+
+    /// Write the element to a [`std::fmt::Write`], such as a [`String`].
+    ///
+    /// This produces the same output as [`Self::write`], without going through bytes.
+    /// Errors from the formatter are returned as [`std::io::Error`]s, as with other
+    /// rendering errors.
+    #[cfg(feature = "std")]
+    pub fn write_fmt(&self, f: &mut dyn std::fmt::Write, depth: usize) -> std::io::Result<()> {
+        self.write_fmt_with_options(f, depth, &RenderOptions::default())
+    }
+
+    /// Write the element to a [`std::fmt::Write`] using the given [`RenderOptions`].
+    #[cfg(feature = "std")]
+    pub fn write_fmt_with_options(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        depth: usize,
+        options: &RenderOptions,
+    ) -> std::io::Result<()> {
+        with_column_tracking(&mut FmtSink(f), options, |sink| {
+            self.write_inner(sink, depth, options, false)
+        })
+    }
+
+    /// Write the element to a [`core::fmt::Write`] using the given [`RenderOptions`].
+    ///
+    /// This is like [`Self::write_fmt_with_options`], but returns a [`core::fmt::Result`],
+    /// and is available without the `std` feature. Rendering errors, such as a void element
+    /// with children, are reported as [`core::fmt::Error`].
+    pub fn format_with_options(
+        &self,
+        f: &mut dyn core::fmt::Write,
+        depth: usize,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        with_column_tracking(&mut FmtSink(f), options, |sink| {
+            self.write_inner(sink, depth, options, false)
+        })
+        .map_err(|_| core::fmt::Error)
+    }
+
+    /// Render the element piece by piece, passing each chunk of HTML to `f` as it is
+    /// produced, instead of buffering the whole output.
+    ///
+    /// The concatenation of the chunks is identical to the output of
+    /// [`Self::write_with_options`]. Returning an error from `f` stops rendering and
+    /// returns that error.
+    #[cfg(feature = "std")]
+    pub fn for_each_chunk(
+        &self,
+        options: &RenderOptions,
+        f: impl FnMut(&str) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        with_column_tracking(&mut ChunkSink(f), options, |sink| {
+            self.write_inner(sink, 0, options, false)
+        })
+    }
+
+    /// Write an attribute, without any preceding whitespace.
+    fn write_attribute(
+        writer: &mut dyn WriteSink,
+        Attribute { key, value }: &Attribute,
+        options: &RenderOptions,
+        declaration: bool,
+    ) -> RenderResult<()> {
+        match value {
+            Some(value) => {
+                let quote = options.attr_quote.as_char();
+                write!(writer, "{}={quote}", key.as_str())?;
+                match value {
+                    AttributeValue::String(s) => {
+                        let escaped = match options.attr_quote {
+                            AttributeQuote::Double => {
+                                html_escape::encode_double_quoted_attribute(s.as_str())
+                            }
+                            AttributeQuote::Single => {
+                                html_escape::encode_single_quoted_attribute(s.as_str())
+                            }
+                        };
+                        write!(writer, "{escaped}")?
+                    }
+                    AttributeValue::Int(i) => write!(writer, "{}", i)?,
+                    AttributeValue::Float(f) => write!(writer, "{}", f)?,
+                    AttributeValue::Bool(b) => write!(writer, "{}", b)?,
+                }
+                write!(writer, "{quote}")
+            }
+            None => {
+                let quote = options.attr_quote.as_char();
+                match options.boolean_attr_style {
+                    _ if declaration => write!(writer, "{}", key.as_str()),
+                    BooleanAttrStyle::Bare => write!(writer, "{}", key.as_str()),
+                    BooleanAttrStyle::Empty => write!(writer, "{}={quote}{quote}", key.as_str()),
+                    BooleanAttrStyle::Repeated => {
+                        write!(writer, "{key}={quote}{key}{quote}", key = key.as_str())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write the element to a writer. If `preserve_whitespace` is set, the element is
+    /// within a [whitespace-preserving](Self::preserves_whitespace) element, and no
+    /// whitespace is added or removed.
+    fn write_inner(
+        &self,
+        writer: &mut dyn WriteSink,
+        depth: usize,
+        options: &RenderOptions,
+        preserve_whitespace: bool,
+    ) -> RenderResult<()> {
         match self {
             RenderElement::Tag {
                 name,
                 attributes,
                 children,
                 void,
+                ..
             } => {
                 // start tag
                 write!(writer, "<{}", name.as_str())?;
-                for Attribute { key, value } in attributes.iter() {
-                    match value {
-                        Some(value) => {
-                            write!(writer, " {}=\"", key.as_str())?;
-                            match value {
-                                AttributeValue::String(s) => write!(
-                                    writer,
-                                    "{}",
-                                    html_escape::encode_quoted_attribute(s.as_str())
-                                )?,
-                                AttributeValue::Int(i) => write!(writer, "{}", i)?,
-                                AttributeValue::Float(f) => write!(writer, "{}", f)?,
-                                AttributeValue::Bool(b) => write!(writer, "{}", b)?,
-                            }
-                            write!(writer, "\"")?;
+                // Declarations such as `<!DOCTYPE html>` are not affected by attribute or
+                // self-closing options
+                let declaration = name.starts_with('!');
+                let multiline_attrs = options.pretty
+                    && !declaration
+                    && options
+                        .multiline_attrs_threshold
+                        .is_some_and(|threshold| attributes.len() > threshold);
+                let write_attribute = |writer: &mut dyn WriteSink, attribute| {
+                    if multiline_attrs {
+                        writeln!(writer)?;
+                        for _ in 0..depth + 1 {
+                            write!(writer, "  ")?;
                         }
-                        None => write!(writer, " {}", key.as_str())?,
+                    } else {
+                        write!(writer, " ")?;
+                    }
+                    Self::write_attribute(writer, attribute, options, declaration)
+                };
+                // Only allocate when the attributes need to be reordered
+                if options.sort_attrs {
+                    let mut sorted_attributes: Vec<_> = attributes.iter().collect();
+                    sorted_attributes.sort_by(|a, b| a.sort_order(b));
+                    for attribute in sorted_attributes {
+                        write_attribute(writer, attribute)?;
+                    }
+                } else {
+                    for attribute in attributes {
+                        write_attribute(writer, attribute)?;
                     }
                 }
-                write!(writer, ">")?;
+                if multiline_attrs {
+                    writeln!(writer)?;
+                    for _ in 0..depth {
+                        write!(writer, "  ")?;
+                    }
+                }
+                let void = if options.self_close_empty && !declaration {
+                    children.is_empty()
+                } else {
+                    *void
+                };
+                if void && (options.self_close_void || options.self_close_empty) && !declaration {
+                    write!(writer, "/>")?;
+                } else {
+                    write!(writer, ">")?;
+                }
 
-                if *void {
+                if void {
                     if !children.is_empty() {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("Void element ({self:?}) has children"),
-                        ));
+                        return Err(void_with_children_error(self));
                     }
                     return Ok(());
                 }
 
-                let did_indent = Self::write_many(writer, children.as_slice(), depth + 1)?;
+                let preserve_whitespace = preserve_whitespace || self.preserves_whitespace();
+                let wrap_width = options.max_inline_width.filter(|_| {
+                    options.pretty && !preserve_whitespace && !self.is_inline_element()
+                });
+                let did_indent = match (wrap_width, writer.column()) {
+                    (Some(width), Some(column)) => Self::write_wrapped_inner(
+                        writer,
+                        children.as_slice(),
+                        depth + 1,
+                        options,
+                        width,
+                        // Leave room for the end tag
+                        column + name.chars().count() + 3,
+                    )?,
+                    _ => Self::write_many_inner(
+                        writer,
+                        children.as_slice(),
+                        depth + 1,
+                        options,
+                        preserve_whitespace,
+                    )?,
+                };
 
                 // end tag
                 if did_indent {
@@ -137,6 +670,10 @@ impl<'bump> RenderElement<'bump> {
                 write!(writer, "</{}>", name.as_str())?;
                 Ok(())
             }
+            RenderElement::Text { text } if preserve_whitespace => {
+                write!(writer, "{}", html_escape::encode_text(text.as_str()))?;
+                Ok(())
+            }
             RenderElement::Text { text } => {
                 let text = html_escape::encode_text(text.as_str());
                 for (idx, line) in text.lines().enumerate() {
@@ -157,37 +694,245 @@ impl<'bump> RenderElement<'bump> {
     /// Write a list of [`RenderElement`]s to a writer.
     ///
     /// Returns whether or not the result was indented.
+    #[cfg(feature = "std")]
     pub fn write_many(
-        writer: &mut dyn Write,
+        writer: &mut dyn std::io::Write,
         elements: &[RenderElement<'bump>],
         depth: usize,
     ) -> std::io::Result<bool> {
-        let should_indent = !elements.is_empty();
+        Self::write_many_with_options(writer, elements, depth, &RenderOptions::default())
+    }
+
+    /// Write a list of [`RenderElement`]s to a writer using the given [`RenderOptions`].
+    ///
+    /// Returns whether or not the result was indented.
+    #[cfg(feature = "std")]
+    pub fn write_many_with_options(
+        writer: &mut dyn std::io::Write,
+        elements: &[RenderElement<'bump>],
+        depth: usize,
+        options: &RenderOptions,
+    ) -> std::io::Result<bool> {
+        with_column_tracking(&mut IoSink(writer), options, |sink| {
+            Self::write_many_inner(sink, elements, depth, options, false)
+        })
+    }
+
+    /// Write a list of [`RenderElement`]s to a [`std::fmt::Write`] using the given
+    /// [`RenderOptions`].
+    ///
+    /// Returns whether or not the result was indented.
+    #[cfg(feature = "std")]
+    pub fn write_many_fmt_with_options(
+        f: &mut dyn std::fmt::Write,
+        elements: &[RenderElement<'bump>],
+        depth: usize,
+        options: &RenderOptions,
+    ) -> std::io::Result<bool> {
+        with_column_tracking(&mut FmtSink(f), options, |sink| {
+            Self::write_many_inner(sink, elements, depth, options, false)
+        })
+    }
+
+    /// Write a list of [`RenderElement`]s to a [`core::fmt::Write`] using the given
+    /// [`RenderOptions`].
+    ///
+    /// This is like [`Self::write_many_fmt_with_options`], but returns a
+    /// [`core::fmt::Result`], and is available without the `std` feature.
+    pub fn format_many_with_options(
+        f: &mut dyn core::fmt::Write,
+        elements: &[RenderElement<'bump>],
+        depth: usize,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        Self::write_many_to_sink(&mut FmtSink(f), elements, depth, options)
+            .map(|_| ())
+            .map_err(|_| core::fmt::Error)
+    }
+
+    pub(crate) fn write_many_to_sink(
+        sink: &mut dyn WriteSink,
+        elements: &[RenderElement<'bump>],
+        depth: usize,
+        options: &RenderOptions,
+    ) -> RenderResult<bool> {
+        with_column_tracking(sink, options, |sink| {
+            Self::write_many_inner(sink, elements, depth, options, false)
+        })
+    }
+
+    fn write_many_inner(
+        writer: &mut dyn WriteSink,
+        elements: &[RenderElement<'bump>],
+        depth: usize,
+        options: &RenderOptions,
+        preserve_whitespace: bool,
+    ) -> RenderResult<bool> {
+        let remove_whitespace =
+            !preserve_whitespace && options.collapse_whitespace == CollapseWhitespace::Remove;
+        let elements = elements
+            .iter()
+            .filter(|e| !(remove_whitespace && e.is_whitespace_text()));
+
+        // Fast path: if there are no block-level children, nothing is indented, so write
+        // them back-to-back without tracking indentation state
+        if !options.pretty
+            || preserve_whitespace
+            || depth == 0
+            || elements.clone().all(Self::is_inline_content)
+        {
+            for element in elements {
+                element.write_inner(writer, depth, options, preserve_whitespace)?;
+            }
+            return Ok(false);
+        }
+
+        // Otherwise, block-level and raw children are placed on their own lines, up until
+        // the first text child
         let mut did_indent = false;
         let mut encountered_text_element = false;
         for element in elements {
             encountered_text_element |= matches!(element, Self::Text { .. });
-            let should_indent_this_child = should_indent
-                && !encountered_text_element
-                && !element.is_inline_element()
-                && !element.is_raw();
-            if should_indent_this_child && depth > 0 {
+            if !encountered_text_element && !element.is_inline_element() {
                 writeln!(writer)?;
                 for _ in 0..depth {
                     write!(writer, "  ")?;
                 }
                 did_indent = true;
             }
-            element.write(writer, depth)?;
+            element.write_inner(writer, depth, options, preserve_whitespace)?;
         }
         Ok(did_indent)
     }
 
+    /// Write a list of inline [`RenderElement`]s on the current line if they fit within
+    /// `width` starting from `column`, and otherwise fill them across indented lines,
+    /// breaking only at existing whitespace.
+    ///
+    /// Falls back to [`Self::write_many_inner`] if any element is not inline content.
+    fn write_wrapped_inner(
+        writer: &mut dyn WriteSink,
+        elements: &[RenderElement<'bump>],
+        depth: usize,
+        options: &RenderOptions,
+        width: usize,
+        column: usize,
+    ) -> RenderResult<bool> {
+        if !elements.iter().all(Self::is_inline_content) {
+            return Self::write_many_inner(writer, elements, depth, options, false);
+        }
+
+        // Inline children are rendered without wrapping; only this level is wrapped
+        let unwrapped_options = RenderOptions {
+            max_inline_width: None,
+            ..options.clone()
+        };
+        let mut single_line = String::new();
+        Self::write_many_inner(
+            &mut FmtSink(&mut single_line),
+            elements,
+            depth,
+            &unwrapped_options,
+            false,
+        )?;
+        if column + single_line.chars().count() <= width {
+            writer.write_str(&single_line)?;
+            return Ok(false);
+        }
+
+        // Split the content into words at the ASCII whitespace in text children. Other
+        // whitespace, such as non-breaking spaces, is not a valid break point in HTML
+        let mut words = vec![];
+        let mut word = String::new();
+        for element in elements {
+            match element {
+                Self::Text { text } => {
+                    let text = html_escape::encode_text(text.as_str());
+                    for (idx, part) in text.split([' ', '\t', '\n', '\x0C', '\r']).enumerate() {
+                        if idx > 0 && !word.is_empty() {
+                            words.push(core::mem::take(&mut word));
+                        }
+                        word.push_str(part);
+                    }
+                }
+                _ => element.write_inner(
+                    &mut FmtSink(&mut word),
+                    depth,
+                    &unwrapped_options,
+                    false,
+                )?,
+            }
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+
+        // Fill each line with as many words as fit
+        let indent = depth * 2;
+        let mut line_width = 0;
+        for word in &words {
+            let word_width = word.chars().count();
+            if line_width > 0 && indent + line_width + 1 + word_width > width {
+                line_width = 0;
+            }
+            if line_width == 0 {
+                writeln!(writer)?;
+                for _ in 0..depth {
+                    write!(writer, "  ")?;
+                }
+            } else {
+                write!(writer, " ")?;
+                line_width += 1;
+            }
+            writer.write_str(word)?;
+            line_width += word_width;
+        }
+        Ok(!words.is_empty())
+    }
+
+    // Whether this element is text consisting only of whitespace
+    fn is_whitespace_text(&self) -> bool {
+        matches!(self, Self::Text { text } if text.chars().all(char::is_whitespace))
+    }
+
+    // Whether this element is an inline element, text, or raw HTML
+    fn is_inline_content(&self) -> bool {
+        match self {
+            Self::Tag { .. } => self.is_inline_element(),
+            Self::Text { .. } | Self::Raw { .. } => true,
+        }
+    }
+
     /// Write a list of [`RenderElement`]s to a string.
+    #[cfg(feature = "std")]
     pub fn write_many_to_string(elements: &[RenderElement<'bump>]) -> std::io::Result<String> {
-        let mut output = vec![];
-        Self::write_many(&mut output, elements, 0)?;
-        Ok(String::from_utf8(output).unwrap())
+        let mut output = String::new();
+        Self::write_many_fmt_with_options(&mut output, elements, 0, &RenderOptions::default())?;
+        Ok(output)
+    }
+
+    /// Visit this element and all of its descendants in pre-order, allowing `f` to modify
+    /// each of them in place before rendering.
+    ///
+    /// `f` is called on an element before its children, and the children visited are those
+    /// present after `f` returns. This allows, for example, replacing the contents of a
+    /// `<code>` element with highlighted [`Raw`](RenderElement::Raw) HTML. New text should be
+    /// allocated in the same [`Bump`] as the rest of the tree.
+    pub fn visit_mut<F: FnMut(&mut RenderElement<'bump>)>(&mut self, f: &mut F) {
+        f(self);
+        if let RenderElement::Tag { children, .. } = self {
+            Self::visit_many_mut(children, f);
+        }
+    }
+
+    /// Visit each of the given elements with [`Self::visit_mut`].
+    pub fn visit_many_mut<F: FnMut(&mut RenderElement<'bump>)>(
+        elements: &mut [RenderElement<'bump>],
+        f: &mut F,
+    ) {
+        for element in elements {
+            element.visit_mut(f);
+        }
     }
 
     /// Get the tag name of the element if it is a [`Tag`].
@@ -198,17 +943,55 @@ impl<'bump> RenderElement<'bump> {
         }
     }
 
+    /// Get the key of the element if it is a [`Tag`](RenderElement::Tag) with a key.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            RenderElement::Tag { key, .. } => key.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the inner text of the element.
+    ///
+    /// This will return an empty string if no inner text exists. [`Raw`] elements
+    /// do not contribute any text.
+    ///
+    /// [`Raw`]: RenderElement::Raw
+    pub fn inner_text(&self, bump: &'bump Bump) -> BumpString<'bump> {
+        match self {
+            RenderElement::Tag { children, .. } => {
+                let mut result = BumpString::new_in(bump);
+                for child in children.iter() {
+                    result.push_str(child.inner_text(bump).as_str());
+                }
+                result
+            }
+            RenderElement::Text { text } => {
+                let mut result = BumpString::new_in(bump);
+                result.push_str(text.as_str());
+                result
+            }
+            RenderElement::Raw { .. } => BumpString::new_in(bump),
+        }
+    }
+
+    /// Returns `true` if the element is a tag whose contents are whitespace-sensitive
+    /// (`<pre>` and `<textarea>`).
+    ///
+    /// No indentation is added within these elements, and their text is written verbatim.
+    pub fn preserves_whitespace(&self) -> bool {
+        self.tag().is_some_and(|t| ["pre", "textarea"].contains(&t))
+    }
+
     /// Returns `true` if the element-with-tag is an inline element.
     pub fn is_inline_element(&self) -> bool {
-        self.tag().is_some_and(|t| {
-            [
-                "a", "abbr", "acronym", "b", "bdo", "big", "br", "button", "cite", "code", "dfn",
-                "em", "i", "img", "input", "kbd", "label", "map", "pre", "object", "output", "q",
-                "samp", "script", "select", "small", "span", "strong", "sub", "sup", "textarea",
-                "time", "tt", "var",
-            ]
-            .contains(&t)
-        })
+        self.tag().is_some_and(crate::builder::is_inline_tag)
+    }
+
+    /// Returns `true` if the element-with-tag is a block-level element, i.e. any tag that
+    /// is not an inline element.
+    pub fn is_block_element(&self) -> bool {
+        self.tag().is_some() && !self.is_inline_element()
     }
 
     /// Returns `true` if the element is [`Raw`].
@@ -255,4 +1038,451 @@ mod tests {
         let output = RenderElement::write_many_to_string(render_elements.as_slice()).unwrap();
         assert_eq!(output, r#"test <a href="https://example.com">tested</a>!"#);
     }
+
+    #[test]
+    pub fn inner_text_skips_raw_elements() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([])([
+            b.h1([])(b.text("Title")),
+            b.fragment([b.text(" and "), b.raw("<em>raw</em>")]),
+            b.p([])([b.text("body "), b.strong([])(b.text("text"))]),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        assert_eq!(
+            render_elements[0].inner_text(&bump).as_str(),
+            "Title and body text"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serializes_to_json() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element =
+            b.div([b.attr(("id", "main")), b.attr("hidden")])([b.text("Hello"), b.raw("<br>")]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+
+        assert_eq!(
+            serde_json::to_value(&render_elements[0]).unwrap(),
+            serde_json::json!({
+                "type": "Tag",
+                "name": "div",
+                "attributes": [
+                    { "key": "id", "value": { "type": "String", "value": "main" } },
+                    { "key": "hidden", "value": null }
+                ],
+                "children": [
+                    { "type": "Text", "text": "Hello" },
+                    { "type": "Raw", "html": "<br>" }
+                ],
+                "void": false
+            })
+        );
+    }
+
+    #[test]
+    pub fn preserves_whitespace_in_pre() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([])([
+            b.p([])(b.text("Code:")),
+            b.pre([])(b.code([])(b.text("  fn main() {\n      run();\n  }\n"))),
+            b.tag("textarea", [], false)(b.text("\n  indented\n")),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        let output = RenderElement::write_many_to_string(render_elements.as_slice()).unwrap();
+        assert_eq!(
+            output,
+            "<div>\n  <p>Code:</p><pre><code>  fn main() {\n      run();\n  }\n</code></pre><textarea>\n  indented\n</textarea>\n</div>"
+        );
+    }
+
+    #[test]
+    pub fn wont_indent_block_elements_in_pre() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.pre([])([b.div([])(b.text("a")), b.div([])(b.text("b"))]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        let output = RenderElement::write_many_to_string(render_elements.as_slice()).unwrap();
+        assert_eq!(output, "<pre><div>a</div><div>b</div></pre>");
+    }
+
+    #[test]
+    pub fn inline_and_block_children_render_consistently() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        // Only inline, text and raw children: written back-to-back
+        let inline = b.p([])([
+            b.em([])(b.text("a")),
+            b.text(" "),
+            b.raw("<wbr>"),
+            b.span([])(b.text("b")),
+        ]);
+        // A block child before any text: each child is indented
+        let block = b.div([])([
+            b.p([])(b.text("c")),
+            b.span([])(b.text("d")),
+            b.p([])(b.text("e")),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [b.div([])([inline, block])]);
+        let output = RenderElement::write_many_to_string(render_elements.as_slice()).unwrap();
+        assert_eq!(
+            output,
+            "<div>\n  <p><em>a</em> <wbr><span>b</span></p>\n  <div>\n    <p>c</p><span>d</span>\n    <p>e</p>\n  </div>\n</div>"
+        );
+    }
+
+    #[test]
+    pub fn inline_element_set() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let is_inline = |name: &str| {
+            RenderElement::from_elements(&bump, [b.tag(name, [], false)([])])[0].is_inline_element()
+        };
+        for name in [
+            "a", "abbr", "acronym", "b", "bdo", "big", "br", "button", "cite", "code", "dfn", "em",
+            "i", "img", "input", "kbd", "label", "map", "pre", "object", "output", "q", "samp",
+            "script", "select", "small", "span", "strong", "sub", "sup", "textarea", "time", "tt",
+            "var",
+        ] {
+            assert!(is_inline(name), "{name} should be inline");
+        }
+        for name in [
+            "div", "p", "section", "ul", "li", "table", "h1", "body", "SPAN",
+        ] {
+            assert!(!is_inline(name), "{name} should not be inline");
+        }
+        assert!(!RenderElement::Text {
+            text: BumpString::from_str_in("span", &bump)
+        }
+        .is_inline_element());
+    }
+
+    #[test]
+    pub fn keeps_whitespace_only_text_by_default() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([])([
+            b.span([])(b.text("a")),
+            b.text("\t"),
+            b.span([])(b.text("b")),
+            b.text("\n\n"),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        assert_eq!(
+            render_elements[0].to_string(),
+            "<div><span>a</span>\t<span>b</span>\n</div>"
+        );
+    }
+
+    #[test]
+    pub fn removes_whitespace_only_text_when_collapsing() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([])([
+            b.text("\n\n"),
+            b.p([])(b.text("a")),
+            b.text("\t"),
+            b.p([])(b.text("b")),
+            b.pre([])(b.text("\t")),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        let options = RenderOptions {
+            collapse_whitespace: CollapseWhitespace::Remove,
+            ..RenderOptions::pretty()
+        };
+        let mut output = vec![];
+        RenderElement::write_many_with_options(&mut output, &render_elements, 0, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "<div>\n  <p>a</p>\n  <p>b</p><pre>\t</pre>\n</div>"
+        );
+    }
+
+    #[test]
+    pub fn escapes_only_the_active_attribute_quote() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let render_elements =
+            RenderElement::from_elements(&bump, [b.span([b.attr(("title", r#"a "b" 'c'"#))])([])]);
+        let render = |attr_quote| {
+            let mut output = vec![];
+            let options = RenderOptions {
+                attr_quote,
+                ..RenderOptions::pretty()
+            };
+            RenderElement::write_many_with_options(&mut output, &render_elements, 0, &options)
+                .unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(
+            render(AttributeQuote::Double),
+            r#"<span title="a &quot;b&quot; 'c'"></span>"#
+        );
+        assert_eq!(
+            render(AttributeQuote::Single),
+            r#"<span title='a "b" &#x27;c&#x27;'></span>"#
+        );
+    }
+
+    #[test]
+    pub fn places_raw_on_its_own_line_among_blocks() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.div([])([
+            b.p([])(b.text("Before")),
+            b.raw_fragments([r#"<pre class="highlight">fn main() {}</pre>"#, "<hr>"]),
+            b.p([])(b.text("After")),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        let output = RenderElement::write_many_to_string(render_elements.as_slice()).unwrap();
+        assert_eq!(
+            output,
+            "<div>\n  <p>Before</p>\n  <pre class=\"highlight\">fn main() {}</pre>\n  <hr>\n  <p>After</p>\n</div>"
+        );
+    }
+
+    #[test]
+    pub fn renders_template_content_as_blocks() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let element = b.body([])([
+            b.ul([b.attr(("id", "list"))])([]),
+            b.template([b.attr(("id", "row"))])(b.li([])([
+                b.span([])(b.text("Name")),
+                b.text(" item"),
+            ])),
+        ]);
+        let render_elements = RenderElement::from_elements(&bump, [element]);
+        let output = RenderElement::write_many_to_string(render_elements.as_slice()).unwrap();
+        assert_eq!(
+            output,
+            "<body>\n  <ul id=\"list\"></ul>\n  <template id=\"row\">\n    <li><span>Name</span> item</li>\n  </template>\n</body>"
+        );
+    }
+
+    #[test]
+    pub fn sorts_attributes_when_requested() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let mut element = b.div([
+            b.attr(("id", "main")),
+            b.attr("hidden"),
+            b.attr(("class", "card")),
+            b.attr(("data-x", "1")),
+        ])(b.input([b.attr(("type", "text")), b.attr("disabled")]));
+        let render_elements = RenderElement::from_elements(&bump, [element.clone()]);
+        let render = |sort_attrs| {
+            let mut output = vec![];
+            let options = RenderOptions {
+                sort_attrs,
+                ..RenderOptions::compact()
+            };
+            RenderElement::write_many_with_options(&mut output, &render_elements, 0, &options)
+                .unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        let sorted =
+            r#"<div class="card" data-x="1" hidden id="main"><input disabled type="text"></div>"#;
+        assert_eq!(
+            render(false),
+            r#"<div id="main" hidden class="card" data-x="1"><input type="text" disabled></div>"#
+        );
+        assert_eq!(render(true), sorted);
+
+        element.sort_attributes();
+        assert_eq!(element.to_string(), sorted);
+    }
+
+    #[test]
+    pub fn options_for_terminal() {
+        assert_eq!(RenderOptions::for_terminal(true), RenderOptions::pretty());
+        assert_eq!(RenderOptions::for_terminal(false), RenderOptions::compact());
+    }
+
+    #[test]
+    pub fn visit_mut_rewrites_code_text() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let mut render_elements = RenderElement::from_elements(
+            &bump,
+            [b.div([])([
+                b.p([])([b.text("Call "), b.code([])(b.text("main()"))]),
+                b.pre([])(b.code([])([b.text("let x = 1;"), b.em([])(b.text("y"))])),
+            ])],
+        );
+
+        RenderElement::visit_many_mut(&mut render_elements, &mut |element| {
+            if element.tag() != Some("code") {
+                return;
+            }
+            let RenderElement::Tag { children, .. } = element else {
+                return;
+            };
+            for child in children.iter_mut() {
+                if let RenderElement::Text { text } = child {
+                    *text = BumpString::from_str_in(&text.to_uppercase(), &bump);
+                }
+            }
+        });
+
+        assert_eq!(
+            RenderElement::write_many_to_string(&render_elements).unwrap(),
+            "<div>\n  <p>Call <code>MAIN()</code></p><pre><code>LET X = 1;<em>y</em></code></pre>\n</div>"
+        );
+    }
+
+    #[test]
+    fn wraps_inline_content_at_max_inline_width() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let words = ["alpha", "beta", "gamma", "delta", "epsilon", "zeta"];
+        let paragraph = b.div([])(b.p([])(b.fragment(words.iter().enumerate().map(
+            |(i, w)| {
+                b.fragment([
+                    if i > 0 { b.text(" ") } else { Element::Empty },
+                    b.span([])(b.text(w)),
+                ])
+            },
+        ))));
+        let short = b.div([])(b.p([])(b.text("Short enough")));
+        let render_elements = RenderElement::from_elements(&bump, [paragraph, short]);
+        let options = RenderOptions {
+            max_inline_width: Some(40),
+            ..RenderOptions::pretty()
+        };
+
+        let mut output = String::new();
+        RenderElement::write_many_fmt_with_options(&mut output, &render_elements, 0, &options)
+            .unwrap();
+        assert_eq!(
+            output,
+            "<div>\n  <p>\n    <span>alpha</span> <span>beta</span>\n    <span>gamma</span>\n    <span>delta</span>\n    <span>epsilon</span>\n    <span>zeta</span>\n  </p>\n</div><div>\n  <p>Short enough</p>\n</div>"
+        );
+        assert!(output.lines().all(|line| line.chars().count() <= 40));
+
+        // Off by default
+        let output = RenderElement::write_many_to_string(&render_elements).unwrap();
+        assert!(output.contains("<p><span>alpha</span> <span>beta</span> <span>gamma</span>"));
+    }
+
+    #[test]
+    fn does_not_wrap_at_non_breaking_spaces() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let render_elements =
+            RenderElement::from_elements(&bump, [b.p([])(b.text("The route is 10\u{a0}km long"))]);
+        let options = RenderOptions {
+            max_inline_width: Some(16),
+            ..RenderOptions::pretty()
+        };
+
+        let mut output = String::new();
+        RenderElement::write_many_fmt_with_options(&mut output, &render_elements, 0, &options)
+            .unwrap();
+        assert_eq!(output, "<p>\n  The route is\n  10\u{a0}km long\n</p>");
+    }
+
+    #[test]
+    fn classifies_inline_and_block_elements() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let render = |element| RenderElement::from_elements(&bump, [element]).remove(0);
+
+        let div = render(b.div([])(Element::Empty));
+        assert!(div.is_block_element());
+        assert!(!div.is_inline_element());
+
+        let span = render(b.span([])(Element::Empty));
+        assert!(!span.is_block_element());
+        assert!(span.is_inline_element());
+
+        assert!(crate::builder::INLINE_TAGS.contains(&"span"));
+        assert!(!crate::builder::INLINE_TAGS.contains(&"div"));
+        for tag in crate::builder::INLINE_TAGS {
+            assert!(render(b.tag(tag, [], false)(Element::Empty)).is_inline_element());
+        }
+
+        let text = render(b.text("text"));
+        assert!(!text.is_block_element() && !text.is_inline_element());
+    }
+
+    #[test]
+    fn writes_boolean_attributes_in_each_style() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let render_elements = RenderElement::from_elements(
+            &bump,
+            [b.input([b.attr("disabled"), b.attr(("type", "text"))])],
+        );
+        let render = |boolean_attr_style, attr_quote| {
+            let options = RenderOptions {
+                boolean_attr_style,
+                attr_quote,
+                ..RenderOptions::default()
+            };
+            let mut output = String::new();
+            RenderElement::write_many_fmt_with_options(&mut output, &render_elements, 0, &options)
+                .unwrap();
+            output
+        };
+
+        assert_eq!(
+            render(BooleanAttrStyle::Bare, AttributeQuote::Double),
+            r#"<input disabled type="text">"#
+        );
+        assert_eq!(
+            render(BooleanAttrStyle::Empty, AttributeQuote::Double),
+            r#"<input disabled="" type="text">"#
+        );
+        assert_eq!(
+            render(BooleanAttrStyle::Repeated, AttributeQuote::Double),
+            r#"<input disabled="disabled" type="text">"#
+        );
+        assert_eq!(
+            render(BooleanAttrStyle::Repeated, AttributeQuote::Single),
+            "<input disabled='disabled' type='text'>"
+        );
+        assert_eq!(
+            RenderOptions::default().boolean_attr_style,
+            BooleanAttrStyle::Bare
+        );
+    }
+
+    #[test]
+    fn writes_attributes_on_separate_lines_above_threshold() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let video = b.video([
+            b.attr(("src", "intro.mp4")),
+            b.attr(("poster", "intro.jpg")),
+            b.attr(("width", "640")),
+            b.attr(("height", "360")),
+            b.attr("controls"),
+            b.attr("muted"),
+        ])(b.p([])(b.text("Unsupported")));
+        let render_elements = RenderElement::from_elements(
+            &bump,
+            [b.section([])([
+                b.h2([b.attr(("id", "intro"))])(b.text("Intro")),
+                video,
+            ])],
+        );
+        let options = RenderOptions {
+            multiline_attrs_threshold: Some(3),
+            ..RenderOptions::pretty()
+        };
+
+        let mut output = String::new();
+        RenderElement::write_many_fmt_with_options(&mut output, &render_elements, 0, &options)
+            .unwrap();
+        assert_eq!(
+            output,
+            "<section>\n  <h2 id=\"intro\">Intro</h2>\n  <video\n    src=\"intro.mp4\"\n    poster=\"intro.jpg\"\n    width=\"640\"\n    height=\"360\"\n    controls\n    muted\n  >\n    <p>Unsupported</p>\n  </video>\n</section>"
+        );
+    }
 }
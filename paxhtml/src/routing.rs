@@ -1,8 +1,19 @@
 use std::{
+    borrow::Cow,
     io::Write,
     path::{Path, PathBuf},
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+/// Whether [`RoutePath::url_path`] ends with a `/` for routes without a filename.
+pub enum TrailingSlash {
+    /// `/blog/`. This is the default.
+    #[default]
+    Always,
+    /// `/blog`. The root route is still `/`.
+    Never,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// A path for a route in an HTML document.
 pub struct RoutePath {
@@ -10,8 +21,16 @@ pub struct RoutePath {
     segments: Vec<String>,
     /// The (optional) filename for this path.
     filename: Option<String>,
+    /// The filename used when `filename` is not present.
+    default_filename: Cow<'static, str>,
+    /// Whether the URL path of a route without a filename ends with a `/`.
+    trailing_slash: TrailingSlash,
 }
 impl RoutePath {
+    /// The filename used for routes without a `filename`, unless overridden with
+    /// [`Self::with_default_filename`].
+    pub const DEFAULT_FILENAME: &'static str = "index.html";
+
     /// Create a new route path from a list of segments.
     pub fn new<'a>(
         segments: impl IntoIterator<Item = &'a str>,
@@ -20,6 +39,8 @@ impl RoutePath {
         Self {
             segments: segments.into_iter().map(|s| s.to_string()).collect(),
             filename: filename.into(),
+            default_filename: Cow::Borrowed(Self::DEFAULT_FILENAME),
+            trailing_slash: TrailingSlash::default(),
         }
     }
     /// Set the `filename` of this [`RoutePath`].
@@ -27,11 +48,57 @@ impl RoutePath {
         self.filename = Some(filename.into());
         self
     }
+    /// Set the filename used when this [`RoutePath`] has no `filename`, such as
+    /// `index.htm`. This defaults to [`Self::DEFAULT_FILENAME`].
+    ///
+    /// This is the file served for the route's directory, so [`Self::url_path`] is
+    /// unaffected.
+    pub fn with_default_filename(mut self, default_filename: impl Into<String>) -> Self {
+        self.default_filename = Cow::Owned(default_filename.into());
+        self
+    }
+    /// Set whether [`Self::url_path`] ends with a `/` when this [`RoutePath`] has no
+    /// `filename`.
+    pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
     /// Get the `filename` of this [`RoutePath`].
     ///
-    /// If no `filename` is present, this will use `index.html` instead.
+    /// If no `filename` is present, this will use the default filename (`index.html`
+    /// unless set with [`Self::with_default_filename`]) instead.
     pub fn filename(&self) -> &str {
-        self.filename.as_deref().unwrap_or("index.html")
+        self.filename.as_deref().unwrap_or(&self.default_filename)
+    }
+    /// Get the extension of [`Self::filename`], without the leading `.`.
+    ///
+    /// Returns `None` if the filename has no extension.
+    pub fn extension(&self) -> Option<&str> {
+        Path::new(self.filename())
+            .extension()
+            .and_then(|extension| extension.to_str())
+    }
+    /// Guess the MIME type of the route's content from [`Self::extension`].
+    ///
+    /// Only common web extensions are recognised (matched case-insensitively):
+    /// - `html`, `htm`: `text/html`
+    /// - `css`: `text/css`
+    /// - `js`, `mjs`: `text/javascript`
+    /// - `json`: `application/json`
+    /// - `xml`: `application/xml`
+    /// - `txt`: `text/plain`
+    /// - `svg`: `image/svg+xml`
+    /// - `png`: `image/png`
+    /// - `jpg`, `jpeg`: `image/jpeg`
+    /// - `wasm`: `application/wasm`
+    ///
+    /// Any other extension, or the lack of one, returns `None`.
+    pub fn guessed_content_type(&self) -> Option<&'static str> {
+        let extension = self.extension()?;
+        CONTENT_TYPES
+            .iter()
+            .find(|(e, _)| e.eq_ignore_ascii_case(extension))
+            .map(|(_, content_type)| *content_type)
     }
     /// Get the directory path for the route (i.e. the directory for which files
     /// should be written to).
@@ -45,7 +112,8 @@ impl RoutePath {
     /// Get the file path for the route (i.e. the path to the file that should
     /// be written to the directory).
     ///
-    /// If no `filename` is present, this will use `index.html` instead.
+    /// If no `filename` is present, this will use the default filename instead; see
+    /// [`Self::filename`].
     pub fn file_path(&self, out_dir: &Path) -> PathBuf {
         self.dir_path(out_dir).join(self.filename())
     }
@@ -67,14 +135,29 @@ impl RoutePath {
     /// Get the URL path for the route (i.e. the path that should be used in the
     /// URL).
     ///
-    /// This always starts and ends with a `/`.
+    /// This always starts with a `/`. If there is no `filename`, it ends with a `/`
+    /// unless [`TrailingSlash::Never`] was set with [`Self::with_trailing_slash`].
+    ///
+    /// The segments and filename are percent-encoded, so that e.g. spaces and non-ASCII
+    /// characters produce a valid URL. [`Self::file_path`] uses them as-is. A `/` within a
+    /// segment is left as a separator, as it nests directories in [`Self::file_path`].
     pub fn url_path(&self) -> String {
-        let mut path = format!("/{}", self.segments.join("/"));
-        if !path.ends_with('/') {
-            path.push('/');
-        }
-        if let Some(filename) = &self.filename {
-            path += filename;
+        let segments: Vec<_> = self.segments.iter().map(|s| percent_encode(s)).collect();
+        let mut path = format!("/{}", segments.join("/"));
+        match &self.filename {
+            Some(filename) => {
+                if !path.ends_with('/') {
+                    path.push('/');
+                }
+                path += &percent_encode(filename);
+            }
+            None => match self.trailing_slash {
+                TrailingSlash::Always if !path.ends_with('/') => path.push('/'),
+                TrailingSlash::Never if path.len() > 1 && path.ends_with('/') => {
+                    path.pop();
+                }
+                _ => {}
+            },
         }
         path
     }
@@ -83,4 +166,219 @@ impl RoutePath {
     pub fn abs_url(&self, domain: &str) -> String {
         format!("{domain}{}", self.url_path())
     }
+    /// Get the `<url>` entry for this route in a sitemap, using [`Self::abs_url`] as its
+    /// location.
+    ///
+    /// `lastmod` should be a W3C datetime, such as `2024-01-31`. See [`sitemap`] to
+    /// produce a complete sitemap.
+    pub fn sitemap_entry(&self, domain: &str, lastmod: Option<&str>) -> String {
+        let mut entry = format!("<url><loc>{}</loc>", escape_xml(&self.abs_url(domain)));
+        if let Some(lastmod) = lastmod {
+            entry += &format!("<lastmod>{}</lastmod>", escape_xml(lastmod));
+        }
+        entry += "</url>";
+        entry
+    }
+}
+
+/// Generate an XML sitemap containing an entry for each route.
+///
+/// `lastmod` is applied to every entry; see [`RoutePath::sitemap_entry`].
+pub fn sitemap(domain: &str, routes: &[RoutePath], lastmod: Option<&str>) -> String {
+    let mut output = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+        "\n"
+    ));
+    for route in routes {
+        output += &route.sitemap_entry(domain, lastmod);
+        output.push('\n');
+    }
+    output += "</urlset>\n";
+    output
+}
+
+// Extensions recognised by `RoutePath::guessed_content_type`
+const CONTENT_TYPES: &[(&str, &str)] = &[
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("txt", "text/plain"),
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("wasm", "application/wasm"),
+];
+
+// Percent-encode everything except the characters permitted in a URL path segment
+fn percent_encode(segment: &str) -> Cow<'_, str> {
+    fn is_allowed(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&byte)
+    }
+
+    if segment.bytes().all(is_allowed) {
+        return Cow::Borrowed(segment);
+    }
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if is_allowed(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded += &format!("%{byte:02X}");
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+fn escape_xml(text: &str) -> std::borrow::Cow<'_, str> {
+    html_escape::encode_quoted_attribute(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_sitemap() {
+        let routes = [
+            RoutePath::new([], None),
+            RoutePath::new(["blog", "fish&chips"], None),
+        ];
+
+        assert_eq!(
+            sitemap("https://example.com", &routes, Some("2024-01-31")),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+                "<url><loc>https://example.com/</loc><lastmod>2024-01-31</lastmod></url>\n",
+                "<url><loc>https://example.com/blog/fish&amp;chips/</loc><lastmod>2024-01-31</lastmod></url>\n",
+                "</urlset>\n"
+            )
+        );
+        assert_eq!(
+            routes[0].sitemap_entry("https://example.com", None),
+            "<url><loc>https://example.com/</loc></url>"
+        );
+    }
+
+    #[test]
+    fn guesses_content_type_from_extension() {
+        let index = RoutePath::new(["blog"], None);
+        assert_eq!(index.extension(), Some("html"));
+        assert_eq!(index.guessed_content_type(), Some("text/html"));
+
+        let feed = RoutePath::new(["blog"], "feed.XML".to_string());
+        assert_eq!(feed.extension(), Some("XML"));
+        assert_eq!(feed.guessed_content_type(), Some("application/xml"));
+
+        let data = RoutePath::new([], "data.json".to_string());
+        assert_eq!(data.guessed_content_type(), Some("application/json"));
+
+        let cname = RoutePath::new([], "CNAME".to_string());
+        assert_eq!(cname.extension(), None);
+        assert_eq!(cname.guessed_content_type(), None);
+    }
+
+    #[test]
+    fn uses_overridden_default_filename() {
+        let out_dir = Path::new("out");
+        let route = RoutePath::new(["blog"], None).with_default_filename("index.htm");
+        assert_eq!(route.filename(), "index.htm");
+        assert_eq!(
+            route.file_path(out_dir),
+            out_dir.join("blog").join("index.htm")
+        );
+        assert_eq!(route.url_path(), "/blog/");
+        assert_eq!(route.guessed_content_type(), Some("text/html"));
+
+        // An explicit filename takes precedence
+        let route = route.with_filename("feed.xml");
+        assert_eq!(
+            route.file_path(out_dir),
+            out_dir.join("blog").join("feed.xml")
+        );
+        assert_eq!(route.url_path(), "/blog/feed.xml");
+
+        assert_eq!(
+            RoutePath::new(["blog"], None).file_path(out_dir),
+            out_dir.join("blog").join(RoutePath::DEFAULT_FILENAME)
+        );
+    }
+
+    #[test]
+    fn applies_trailing_slash_policy() {
+        let route = RoutePath::new(["blog", "post"], None);
+        assert_eq!(route.url_path(), "/blog/post/");
+        assert_eq!(
+            route
+                .clone()
+                .with_trailing_slash(TrailingSlash::Always)
+                .url_path(),
+            "/blog/post/"
+        );
+        let never = route.with_trailing_slash(TrailingSlash::Never);
+        assert_eq!(never.url_path(), "/blog/post");
+        assert_eq!(
+            never.abs_url("https://example.com"),
+            "https://example.com/blog/post"
+        );
+
+        // Routes with a filename are unaffected
+        let with_filename = never.with_filename("feed.xml");
+        assert_eq!(with_filename.url_path(), "/blog/post/feed.xml");
+        assert_eq!(
+            with_filename
+                .with_trailing_slash(TrailingSlash::Always)
+                .url_path(),
+            "/blog/post/feed.xml"
+        );
+
+        // The root route keeps its slash
+        let root = RoutePath::new([], None).with_trailing_slash(TrailingSlash::Never);
+        assert_eq!(root.url_path(), "/");
+    }
+
+    #[test]
+    fn percent_encodes_url_segments() {
+        let route = RoutePath::new(["my posts", "café"], None);
+        assert_eq!(route.url_path(), "/my%20posts/caf%C3%A9/");
+        assert_eq!(
+            route.abs_url("https://example.com"),
+            "https://example.com/my%20posts/caf%C3%A9/"
+        );
+        assert_eq!(
+            route.file_path(Path::new("out")),
+            Path::new("out")
+                .join("my posts")
+                .join("café")
+                .join("index.html")
+        );
+
+        let route = RoutePath::new(["100%", "a?"], "notes #1.txt".to_string());
+        assert_eq!(route.url_path(), "/100%25/a%3F/notes%20%231.txt");
+        assert_eq!(
+            RoutePath::new(["fish&chips", "v1.2_x~y"], None).url_path(),
+            "/fish&chips/v1.2_x~y/"
+        );
+    }
+
+    #[test]
+    fn slash_in_segment_matches_file_path() {
+        let route = RoutePath::new(["a/b"], None);
+        assert_eq!(route.url_path(), "/a/b/");
+        assert_eq!(
+            route.url_path(),
+            RoutePath::new(["a", "b"], None).url_path()
+        );
+        assert_eq!(
+            route.file_path(Path::new("out")),
+            Path::new("out").join("a").join("b").join("index.html")
+        );
+    }
 }
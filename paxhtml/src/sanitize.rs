@@ -0,0 +1,443 @@
+use bumpalo::collections::String as BumpString;
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::{eval::eval_attribute, Attribute, Element, EvalError};
+use paxhtml_parser::AstNode;
+
+/// A policy describing which tags and attributes are permitted when evaluating untrusted
+/// HTML with [`eval_node_sanitized`].
+///
+/// Tags are matched case-insensitively. A tag that is not allowed is handled as follows:
+/// - if it is in [`Self::removed_tags`], it is dropped along with all of its content;
+/// - otherwise, it is unwrapped, so that its (sanitized) children take its place.
+///
+/// Attributes that are not allowed are dropped. These are attributes not in
+/// [`Self::allowed_attributes`], event handlers (attributes starting with `on`) if
+/// [`Self::strip_event_handlers`] is set, and URL attributes such as `href`, `src` and
+/// `srcset` with a URL whose scheme is not in [`Self::allowed_url_schemes`].
+///
+/// If [`Self::error_on_disallowed`] is set, any tag or attribute that is not allowed is
+/// instead reported as an [`EvalError::DisallowedTag`] or [`EvalError::DisallowedAttribute`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    /// The tags that are kept.
+    pub allowed_tags: Vec<String>,
+    /// The disallowed tags whose content is dropped, rather than unwrapped.
+    pub removed_tags: Vec<String>,
+    /// The attributes that are kept, matched case-insensitively, or `None` to keep any
+    /// attribute not dropped by the other rules.
+    pub allowed_attributes: Option<Vec<String>>,
+    /// Whether attributes starting with `on`, such as `onclick`, are dropped.
+    pub strip_event_handlers: bool,
+    /// The URL schemes permitted in URL attributes (e.g. `https`), matched
+    /// case-insensitively. URLs without a scheme, such as relative paths, are always
+    /// permitted.
+    pub allowed_url_schemes: Vec<String>,
+    /// Whether `rel="noopener"` is added to `<a target="_blank">` links.
+    pub enforce_noopener: bool,
+    /// Whether a disallowed tag or attribute is an error, rather than being dropped,
+    /// unwrapped or removed.
+    pub error_on_disallowed: bool,
+}
+impl Default for SanitizePolicy {
+    /// A policy allowing common text formatting, list, table, link and image tags.
+    ///
+    /// `<script>`, `<style>`, and embedding or form tags such as `<iframe>` and `<form>`
+    /// are removed along with their content. Only presentational and link attributes such
+    /// as `class`, `href` and `alt` are kept, so `style`, `id` and `name` are dropped. Event
+    /// handlers are stripped, URLs are limited to the `http`, `https`, `mailto` and `tel`
+    /// schemes, and `noopener` is enforced.
+    fn default() -> Self {
+        let strings = |tags: &[&str]| tags.iter().map(|t| t.to_string()).collect();
+        Self {
+            allowed_tags: strings(&[
+                "a",
+                "abbr",
+                "b",
+                "blockquote",
+                "br",
+                "caption",
+                "cite",
+                "code",
+                "dd",
+                "del",
+                "details",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "i",
+                "img",
+                "ins",
+                "kbd",
+                "li",
+                "mark",
+                "ol",
+                "p",
+                "pre",
+                "q",
+                "s",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "tfoot",
+                "th",
+                "thead",
+                "time",
+                "tr",
+                "u",
+                "ul",
+            ]),
+            removed_tags: strings(&[
+                "script", "style", "iframe", "frame", "frameset", "object", "embed", "applet",
+                "template", "noscript", "form", "input", "button", "select", "textarea", "link",
+                "meta", "base", "title", "svg", "math",
+            ]),
+            allowed_attributes: Some(strings(&[
+                "abbr", "align", "alt", "cite", "class", "colspan", "datetime", "dir", "headers",
+                "height", "href", "hreflang", "lang", "open", "rel", "reversed", "rowspan",
+                "scope", "sizes", "span", "src", "srcset", "start", "target", "title", "type",
+                "width",
+            ])),
+            strip_event_handlers: true,
+            allowed_url_schemes: strings(&["http", "https", "mailto", "tel"]),
+            enforce_noopener: true,
+            error_on_disallowed: false,
+        }
+    }
+}
+impl SanitizePolicy {
+    /// Returns `true` if `tag` is in [`Self::allowed_tags`].
+    pub fn allows_tag(&self, tag: &str) -> bool {
+        self.allowed_tags
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Returns `true` if `attribute` is permitted by this policy.
+    pub fn allows_attribute(&self, attribute: &Attribute) -> bool {
+        let key = attribute.key.as_str();
+        if self
+            .allowed_attributes
+            .as_ref()
+            .is_some_and(|allowed| !allowed.iter().any(|a| a.eq_ignore_ascii_case(key)))
+        {
+            return false;
+        }
+        if self.strip_event_handlers
+            && key.len() > 2
+            && key.get(..2).is_some_and(|p| p.eq_ignore_ascii_case("on"))
+        {
+            return false;
+        }
+        let allows_url = |url: &str| match url_scheme(url) {
+            Some(scheme) => self
+                .allowed_url_schemes
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(&scheme)),
+            None => true,
+        };
+        let value = attribute.value_as_str().unwrap_or_default();
+        if URL_ATTRIBUTES.iter().any(|a| a.eq_ignore_ascii_case(key)) {
+            return allows_url(value);
+        }
+        if key.eq_ignore_ascii_case("srcset") {
+            // Each comma-separated candidate is a URL followed by an optional descriptor
+            return value
+                .split(',')
+                .filter_map(|candidate| candidate.split_whitespace().next())
+                .all(allows_url);
+        }
+        true
+    }
+
+    fn removes_tag(&self, tag: &str) -> bool {
+        self.removed_tags
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+// Attributes whose values are URLs, and are checked against the allowed URL schemes
+const URL_ATTRIBUTES: &[&str] = &[
+    "href",
+    "src",
+    "cite",
+    "action",
+    "formaction",
+    "poster",
+    "background",
+    "longdesc",
+    "xlink:href",
+];
+
+// Extracts the scheme of a URL, ignoring the whitespace and control characters that browsers
+// ignore (e.g. `java\tscript:`). Returns `None` for URLs without a scheme.
+fn url_scheme(url: &str) -> Option<String> {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+    let end = url.find([':', '/', '?', '#'])?;
+    url[end..].starts_with(':').then(|| url[..end].to_string())
+}
+
+/// Convert an AST node to a runtime [`Element`], dropping or unwrapping the tags and
+/// attributes that the given [`SanitizePolicy`] does not allow.
+///
+/// This is intended for untrusted input; see [`crate::parse_plain_html`] to produce an AST
+/// from plain HTML. Raw nodes, such as the contents of `<script>`, are always dropped.
+///
+/// ## Errors
+///
+/// Returns [`EvalError::DisallowedTag`] if [`SanitizePolicy::error_on_disallowed`] is set
+/// and a tag is not allowed, in addition to the errors returned by [`crate::eval_node`].
+pub fn eval_node_sanitized<'bump>(
+    bump: &'bump Bump,
+    node: &AstNode,
+    policy: &SanitizePolicy,
+) -> Result<Element<'bump>, EvalError> {
+    let eval_children = |children: &[AstNode]| -> Result<_, EvalError> {
+        let mut elements = BumpVec::new_in(bump);
+        for child in children {
+            match eval_node_sanitized(bump, child, policy)? {
+                Element::Empty => {}
+                element => elements.push(element),
+            }
+        }
+        Ok(elements)
+    };
+
+    match node {
+        AstNode::Element {
+            name,
+            attributes,
+            children,
+            void,
+        } => {
+            if !policy.allows_tag(name) {
+                if policy.error_on_disallowed {
+                    return Err(EvalError::DisallowedTag { name: name.clone() });
+                }
+                if policy.removes_tag(name) {
+                    return Ok(Element::Empty);
+                }
+                return Ok(Element::Fragment {
+                    children: eval_children(children)?,
+                });
+            }
+
+            let mut attrs = BumpVec::new_in(bump);
+            for attr in attributes {
+                attrs.extend(eval_attribute(bump, attr, Some(policy))?);
+            }
+            if policy.enforce_noopener && name.eq_ignore_ascii_case("a") {
+                add_noopener(bump, &mut attrs);
+            }
+
+            Ok(Element::Tag {
+                name: BumpString::from_str_in(name, bump),
+                attributes: attrs,
+                children: eval_children(children)?,
+                void: *void || crate::builder::is_void_tag(name),
+                key: None,
+            })
+        }
+        AstNode::Fragment(children) => Ok(Element::Fragment {
+            children: eval_children(children)?,
+        }),
+        AstNode::Raw(_) => Ok(Element::Empty),
+        other => crate::eval_node(bump, other),
+    }
+}
+
+// Adds `noopener` to the `rel` of a link with `target="_blank"`, if not already present
+fn add_noopener<'bump>(bump: &'bump Bump, attributes: &mut BumpVec<'bump, Attribute<'bump>>) {
+    let opens_new_context = attributes.iter().any(|a| {
+        a.key.eq_ignore_ascii_case("target")
+            && a.value_as_str()
+                .is_some_and(|v| v.eq_ignore_ascii_case("_blank"))
+    });
+    if !opens_new_context {
+        return;
+    }
+
+    match attributes
+        .iter_mut()
+        .find(|a| a.key.eq_ignore_ascii_case("rel"))
+    {
+        Some(rel) => {
+            let value = rel.value_as_str().unwrap_or_default();
+            if !value
+                .split_ascii_whitespace()
+                .any(|v| v.eq_ignore_ascii_case("noopener"))
+            {
+                let value = if value.trim().is_empty() {
+                    "noopener".to_string()
+                } else {
+                    format!("{value} noopener")
+                };
+                *rel = Attribute::new(bump, "rel", &value);
+            }
+        }
+        None => attributes.push(Attribute::new(bump, "rel", "noopener")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paxhtml_parser::parse_plain_html;
+
+    fn sanitize(html: &str, policy: &SanitizePolicy) -> Result<String, EvalError> {
+        let bump = Bump::new();
+        let ast = parse_plain_html(html).unwrap();
+        let element = eval_node_sanitized(&bump, &ast, policy)?;
+        let html = crate::Document::new(&bump, [element])
+            .write_compact_to_string()
+            .unwrap();
+        Ok(html)
+    }
+
+    #[test]
+    fn default_policy_strips_script_and_keeps_formatting() {
+        let policy = SanitizePolicy::default();
+        assert_eq!(
+            sanitize(
+                "<p><b>Hi</b><script>alert(1)</script><font>there</font><iframe src=x>no</iframe></p>",
+                &policy
+            )
+            .unwrap(),
+            "<p><b>Hi</b>there</p>"
+        );
+    }
+
+    #[test]
+    fn errors_on_disallowed_tags_when_requested() {
+        let policy = SanitizePolicy {
+            error_on_disallowed: true,
+            ..SanitizePolicy::default()
+        };
+        assert_eq!(
+            sanitize("<p><b>Hi</b></p>", &policy).unwrap(),
+            "<p><b>Hi</b></p>"
+        );
+        assert!(matches!(
+            sanitize("<p><SCRIPT>alert(1)</SCRIPT></p>", &policy),
+            Err(EvalError::DisallowedTag { name }) if name.eq_ignore_ascii_case("script")
+        ));
+    }
+
+    #[test]
+    fn default_policy_strips_dangerous_attributes() {
+        let policy = SanitizePolicy::default();
+        assert_eq!(
+            sanitize(
+                r#"<p onclick="steal()" class=x><a href="java&#9;script:alert(1)">a</a><a href="/ok" ONMOUSEOVER=x>b</a></p>"#,
+                &policy
+            )
+            .unwrap(),
+            r#"<p class="x"><a>a</a><a href="/ok">b</a></p>"#
+        );
+        assert_eq!(
+            sanitize(r#"<img src="data:image/png;base64,AAAA" alt=x>"#, &policy).unwrap(),
+            r#"<img alt="x">"#
+        );
+        assert_eq!(
+            sanitize(
+                r#"<a href="https://example.com" target=_blank>x</a>"#,
+                &policy
+            )
+            .unwrap(),
+            r#"<a href="https://example.com" target="_blank" rel="noopener">x</a>"#
+        );
+        assert_eq!(
+            sanitize(r#"<a target=_blank rel=nofollow>x</a>"#, &policy).unwrap(),
+            r#"<a target="_blank" rel="nofollow noopener">x</a>"#
+        );
+    }
+
+    #[test]
+    fn default_policy_only_keeps_allowed_attributes() {
+        let policy = SanitizePolicy::default();
+        assert_eq!(
+            sanitize(
+                r#"<p style="position:fixed;inset:0" id=login name=x TITLE=t>x</p>"#,
+                &policy
+            )
+            .unwrap(),
+            r#"<p TITLE="t">x</p>"#
+        );
+
+        let policy = SanitizePolicy {
+            allowed_attributes: None,
+            ..policy
+        };
+        assert_eq!(
+            sanitize(r#"<p style="color:red" onclick=x>x</p>"#, &policy).unwrap(),
+            r#"<p style="color:red">x</p>"#
+        );
+    }
+
+    #[test]
+    fn checks_each_srcset_url() {
+        let policy = SanitizePolicy::default();
+        assert_eq!(
+            sanitize(
+                r#"<img srcset="/small.png 1x, https://example.com/large.png 2x">"#,
+                &policy
+            )
+            .unwrap(),
+            r#"<img srcset="/small.png 1x, https://example.com/large.png 2x">"#
+        );
+        assert_eq!(
+            sanitize(
+                r#"<img srcset="/small.png 1x, javascript:alert(1) 2x" alt=x>"#,
+                &policy
+            )
+            .unwrap(),
+            r#"<img alt="x">"#
+        );
+    }
+
+    #[test]
+    fn allowed_url_schemes_are_configurable() {
+        let policy = SanitizePolicy {
+            allowed_url_schemes: vec!["https".to_string(), "data".to_string()],
+            ..SanitizePolicy::default()
+        };
+        assert_eq!(
+            sanitize(r#"<img src="DATA:image/png;base64,AAAA">"#, &policy).unwrap(),
+            r#"<img src="DATA:image/png;base64,AAAA">"#
+        );
+        assert_eq!(
+            sanitize(r#"<a href="mailto:a@b.c">x</a>"#, &policy).unwrap(),
+            "<a>x</a>"
+        );
+
+        let policy = SanitizePolicy {
+            error_on_disallowed: true,
+            ..policy
+        };
+        assert!(matches!(
+            sanitize(r#"<b onclick=x>x</b>"#, &policy),
+            Err(EvalError::DisallowedAttribute { name }) if name == "onclick"
+        ));
+    }
+}
@@ -0,0 +1,286 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::Element;
+
+/// A parsed CSS selector, used by [`Element::select`] and [`Element::matches`].
+///
+/// Only a small subset of CSS is supported: type (`div`), class (`.card`) and ID (`#main`)
+/// selectors, compounds of these (`div.card#main`), and the descendant combinator
+/// (`#main p`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Selector {
+    /// The compound selectors, from the outermost ancestor to the subject.
+    compounds: Vec<CompoundSelector>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CompoundSelector {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl Selector {
+    pub(crate) fn parse(selector: &str) -> Result<Self, SelectorError> {
+        let mut compounds = vec![];
+        let mut compound = CompoundSelector::default();
+        let mut has_compound = false;
+
+        let mut chars = selector.char_indices().peekable();
+        while let Some((position, c)) = chars.next() {
+            match c {
+                c if c.is_whitespace() => {
+                    if has_compound {
+                        compounds.push(core::mem::take(&mut compound));
+                        has_compound = false;
+                    }
+                }
+                '.' | '#' => {
+                    let mut name = String::new();
+                    while let Some((_, c)) = chars.next_if(|(_, c)| is_name_char(*c)) {
+                        name.push(c);
+                    }
+                    if name.is_empty() {
+                        return Err(SelectorError::MissingName { position });
+                    }
+                    if c == '.' {
+                        compound.classes.push(name);
+                    } else if compound.id.is_some() {
+                        return Err(SelectorError::Unsupported {
+                            character: c,
+                            position,
+                        });
+                    } else {
+                        compound.id = Some(name);
+                    }
+                    has_compound = true;
+                }
+                c if is_name_char(c) && !has_compound => {
+                    let mut name = c.to_string();
+                    while let Some((_, c)) = chars.next_if(|(_, c)| is_name_char(*c)) {
+                        name.push(c);
+                    }
+                    compound.tag = Some(name);
+                    has_compound = true;
+                }
+                character => {
+                    return Err(SelectorError::Unsupported {
+                        character,
+                        position,
+                    })
+                }
+            }
+        }
+        if has_compound {
+            compounds.push(compound);
+        }
+
+        if compounds.is_empty() {
+            return Err(SelectorError::Empty);
+        }
+        Ok(Self { compounds })
+    }
+
+    /// Whether this selector matches `element`, given its ancestors from the outermost
+    /// to the innermost.
+    pub(crate) fn matches(&self, element: &Element, ancestors: &[&Element]) -> bool {
+        let Some((subject, rest)) = self.compounds.split_last() else {
+            return false;
+        };
+        if !subject.matches(element) {
+            return false;
+        }
+
+        // Match the remaining compounds against ancestors, innermost first. Greedily
+        // taking the closest matching ancestor is sufficient for descendant combinators.
+        let mut ancestors = ancestors.iter().rev();
+        rest.iter()
+            .rev()
+            .all(|compound| ancestors.any(|ancestor| compound.matches(ancestor)))
+    }
+
+    /// Collect all elements in the tree rooted at `element` (including itself) that
+    /// match this selector, in document order.
+    pub(crate) fn select<'a, 'bump>(
+        &self,
+        element: &'a Element<'bump>,
+        ancestors: &mut Vec<&'a Element<'bump>>,
+        results: &mut Vec<&'a Element<'bump>>,
+    ) {
+        if element.is_tag() {
+            if self.matches(element, ancestors) {
+                results.push(element);
+            }
+            ancestors.push(element);
+        }
+        for child in element.children() {
+            self.select(child, ancestors, results);
+        }
+        if element.is_tag() {
+            ancestors.pop();
+        }
+    }
+}
+
+impl CompoundSelector {
+    fn matches(&self, element: &Element) -> bool {
+        let Element::Tag { name, .. } = element else {
+            return false;
+        };
+        if self
+            .tag
+            .as_ref()
+            .is_some_and(|tag| !tag.eq_ignore_ascii_case(name))
+        {
+            return false;
+        }
+        let attr_value = |key: &str| {
+            element
+                .attrs()
+                .unwrap_or_default()
+                .iter()
+                .find(|attr| attr.key.eq_ignore_ascii_case(key))
+                .and_then(|attr| attr.value_as_str())
+                .unwrap_or_default()
+        };
+        if self.id.as_ref().is_some_and(|id| attr_value("id") != id) {
+            return false;
+        }
+        let classes = attr_value("class");
+        self.classes
+            .iter()
+            .all(|class| classes.split_whitespace().any(|c| c == class))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Error returned when a CSS selector could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    /// The selector is empty.
+    Empty,
+    /// A `.` or `#` is not followed by a name.
+    MissingName {
+        /// The byte offset of the `.` or `#`.
+        position: usize,
+    },
+    /// The selector uses syntax that is not supported.
+    Unsupported {
+        /// The character at which the unsupported syntax starts.
+        character: char,
+        /// The byte offset of the character.
+        position: usize,
+    },
+}
+impl core::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SelectorError::Empty => write!(f, "Selector is empty"),
+            SelectorError::MissingName { position } => {
+                write!(f, "Expected a class or ID name at position {position}")
+            }
+            SelectorError::Unsupported {
+                character,
+                position,
+            } => write!(
+                f,
+                "Unsupported selector syntax '{character}' at position {position}"
+            ),
+        }
+    }
+}
+impl core::error::Error for SelectorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+    use bumpalo::Bump;
+
+    #[test]
+    fn parses_compounds_and_descendants() {
+        let selector = Selector::parse(" #main  div.card.wide ").unwrap();
+        assert_eq!(
+            selector.compounds,
+            vec![
+                CompoundSelector {
+                    id: Some("main".to_string()),
+                    ..Default::default()
+                },
+                CompoundSelector {
+                    tag: Some("div".to_string()),
+                    classes: vec!["card".to_string(), "wide".to_string()],
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_syntax() {
+        assert_eq!(Selector::parse("  "), Err(SelectorError::Empty));
+        assert_eq!(
+            Selector::parse("div > p"),
+            Err(SelectorError::Unsupported {
+                character: '>',
+                position: 4
+            })
+        );
+        assert_eq!(
+            Selector::parse("a[href]"),
+            Err(SelectorError::Unsupported {
+                character: '[',
+                position: 1
+            })
+        );
+        assert_eq!(
+            Selector::parse("div.").unwrap_err(),
+            SelectorError::MissingName { position: 3 }
+        );
+        assert!(Selector::parse("#a#b").is_err());
+    }
+
+    #[test]
+    fn selects_matching_elements() {
+        let bump = Bump::new();
+        let b = Builder::new(&bump);
+        let tree = b.body([])([
+            b.div([b.attr(("class", "card featured"))])(b.p([])(b.text("Card"))),
+            b.div([b.attr(("class", "cardboard"))])(Element::Empty),
+            b.main([b.attr(("id", "main"))])(b.fragment([
+                b.p([])(b.text("One")),
+                b.section([])(b.p([])(b.text("Two"))),
+            ])),
+        ]);
+
+        let cards = tree.select("div.card").unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(
+            cards[0].attr("class").unwrap().value_as_str(),
+            Some("card featured")
+        );
+
+        let paragraphs = tree.select("#main p").unwrap();
+        assert_eq!(
+            paragraphs
+                .iter()
+                .map(|p| p.inner_text(&bump).to_string())
+                .collect::<Vec<_>>(),
+            ["One", "Two"]
+        );
+        assert_eq!(tree.select("body #main section p").unwrap().len(), 1);
+        assert!(tree.select("section #main").unwrap().is_empty());
+        assert_eq!(tree.select("P").unwrap().len(), 3);
+
+        assert!(tree.matches("body").unwrap());
+        assert!(!tree.matches(".card").unwrap());
+        assert!(tree.select("div:first-child").is_err());
+    }
+}
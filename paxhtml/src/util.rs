@@ -1,5 +1,7 @@
 //! Utility functions.
 
+use alloc::string::String;
+
 /// Slugify a string.
 ///
 /// This converts a string to a slug by lowercasing it, replacing spaces and
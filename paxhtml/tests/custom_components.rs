@@ -49,6 +49,7 @@ fn Simple<'bump>(bump: &'bump Bump, props: SimpleProps) -> Element<'bump> {
         attributes: BumpVec::new_in(bump),
         children,
         void: false,
+        key: None,
     }
 }
 
@@ -260,6 +261,7 @@ fn test_component_with_kebab_case_attribute() {
             attributes: BumpVec::new_in(bump),
             children,
             void: false,
+            key: None,
         }
     }
 
@@ -296,3 +298,171 @@ fn test_component_without_children() {
         panic!("Expected Tag element");
     }
 }
+
+#[test]
+fn test_component_with_spread_props() {
+    let bump = Bump::new();
+
+    let base = MyCustomElementProps {
+        cool: 1,
+        test: "from spread".to_string(),
+        children: None,
+    };
+
+    // Named attributes override fields from the spread
+    let result = html! { in &bump;
+        <MyCustomElement {..base} cool={7} />
+    };
+
+    if let Element::Tag { children, .. } = &result {
+        if let Element::Tag {
+            children: p_children,
+            ..
+        } = &children[0]
+        {
+            if let Element::Text { text } = &p_children[0] {
+                assert_eq!(text.as_str(), "cool: 7, test: from spread");
+            } else {
+                panic!("Expected text node");
+            }
+        }
+    } else {
+        panic!("Expected Tag element");
+    }
+}
+
+#[derive(DefaultIn)]
+struct DerivedProps<'bump> {
+    title: String,
+    count: i32,
+    children: BumpVec<'bump, Element<'bump>>,
+}
+
+#[allow(non_snake_case)]
+fn Derived<'bump>(bump: &'bump Bump, props: DerivedProps<'bump>) -> Element<'bump> {
+    html! { in bump;
+        <section>
+            <h2>{format!("{} ({})", props.title, props.count)}</h2>
+            {Element::from_iter(bump, props.children)}
+        </section>
+    }
+}
+
+#[derive(DefaultIn)]
+struct DerivedWithoutLifetime {
+    enabled: bool,
+}
+
+#[test]
+fn test_derived_default_in() {
+    let bump = Bump::new();
+
+    let result = html! { in &bump; <Derived title={"Hello".to_string()} /> };
+    assert_eq!(
+        result.to_string(),
+        "<section>\n  <h2>Hello (0)</h2>\n</section>"
+    );
+
+    let props = DerivedProps::default_in(&bump);
+    assert!(props.title.is_empty());
+    assert!(props.children.is_empty());
+    assert!(!DerivedWithoutLifetime::default_in(&bump).enabled);
+}
+
+struct LayoutProps<'bump> {
+    children: Element<'bump>,
+}
+impl<'bump> DefaultIn<'bump> for LayoutProps<'bump> {
+    fn default_in(_bump: &'bump Bump) -> Self {
+        Self {
+            children: Element::Empty,
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn Layout<'bump>(bump: &'bump Bump, props: LayoutProps<'bump>) -> Element<'bump> {
+    html! { in bump; <main>{props.children}</main> }
+}
+
+#[test]
+fn test_component_with_single_element_children() {
+    let bump = Bump::new();
+
+    let single = html! { in &bump; <Layout><p>"Only"</p></Layout> };
+    assert!(matches!(
+        &single,
+        Element::Tag { children, .. } if children.len() == 1 && children[0].is_tag()
+    ));
+    assert_eq!(single.to_string(), "<main>\n  <p>Only</p>\n</main>");
+
+    let multiple = html! { in &bump; <Layout><p>"a"</p><p>"b"</p></Layout> };
+    assert_eq!(
+        multiple.to_string(),
+        "<main>\n  <p>a</p>\n  <p>b</p>\n</main>"
+    );
+
+    let empty = html! { in &bump; <Layout /> };
+    assert_eq!(empty.to_string(), "<main></main>");
+
+    let nested = html! { in &bump;
+        <Derived title={"Vec".to_string()}><p>"a"</p><p>"b"</p></Derived>
+    };
+    assert_eq!(
+        nested.to_string(),
+        "<section>\n  <h2>Vec (0)</h2>\n  <p>a</p>\n  <p>b</p>\n</section>"
+    );
+}
+
+struct CardProps<'bump> {
+    header: Element<'bump>,
+    footer: Option<Element<'bump>>,
+    children: Element<'bump>,
+}
+impl<'bump> DefaultIn<'bump> for CardProps<'bump> {
+    fn default_in(_bump: &'bump Bump) -> Self {
+        Self {
+            header: Element::Empty,
+            footer: None,
+            children: Element::Empty,
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn Card<'bump>(bump: &'bump Bump, props: CardProps<'bump>) -> Element<'bump> {
+    html! { in bump;
+        <div class="card">
+            <header>{props.header}</header>
+            <div class="body">{props.children}</div>
+            {props.footer.map(|footer| html! { in bump; <footer>{footer}</footer> })}
+        </div>
+    }
+}
+
+#[test]
+fn test_component_with_named_slots() {
+    let bump = Bump::new();
+
+    let header = html! { in &bump;
+        <Card>
+            <p>"Body"</p>
+            <slot name="header"><h1>"Title"</h1></slot>
+        </Card>
+    };
+    assert_eq!(
+        header.to_string(),
+        "<div class=\"card\">\n  <header>\n    <h1>Title</h1>\n  </header>\n  <div class=\"body\">\n    <p>Body</p>\n  </div>\n</div>"
+    );
+
+    let with_footer = html! { in &bump;
+        <Card>
+            <slot name="header">"Title"</slot>
+            <slot name="footer"><small>"Fine print"</small></slot>
+        </Card>
+    };
+    assert_eq!(
+        with_footer.to_string(),
+        "<div class=\"card\">\n  <header>Title</header>\n  <div class=\"body\"></div>\n  <footer><small>Fine print</small></footer>\n</div>"
+    );
+}
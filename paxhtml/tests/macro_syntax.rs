@@ -0,0 +1,228 @@
+use bumpalo::Bump;
+use paxhtml::{attr, builder::Builder, html, Attribute, Document, Element};
+
+#[test]
+fn test_spread_attributes_on_regular_tag() {
+    let bump = Bump::new();
+    let extra = [
+        Attribute::new(&bump, "id", "spread"),
+        Attribute::new(&bump, "title", "From spread"),
+    ];
+
+    let element = html! { in &bump;
+        <div {..extra} id="named"></div>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert_eq!(output, r#"<div title="From spread" id="named"></div>"#);
+}
+
+#[test]
+fn test_spread_attributes_named_overrides_regardless_of_order() {
+    let bump = Bump::new();
+    let extra = vec![
+        Attribute::new(&bump, "class", "spread"),
+        Attribute::boolean(&bump, "hidden"),
+    ];
+
+    let element = html! { in &bump;
+        <span class="named" {..extra}>"Text"</span>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert_eq!(output, r#"<span class="named" hidden>Text</span>"#);
+}
+
+#[test]
+fn test_dynamic_tag_name() {
+    let bump = Bump::new();
+    let level = 2;
+    let tag = format!("h{level}");
+
+    let element = html! { in &bump;
+        <{tag.as_str()} class="heading">"Title"</{tag.as_str()}>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert_eq!(output, r#"<h2 class="heading">Title</h2>"#);
+}
+
+#[test]
+fn test_dynamic_tag_name_with_empty_close() {
+    let bump = Bump::new();
+    let tag = "section";
+
+    let element = html! { in &bump;
+        <{tag}><p>"Body"</p></>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert_eq!(output, "<section>\n  <p>Body</p>\n</section>");
+}
+
+#[test]
+fn test_doctype() {
+    let bump = Bump::new();
+
+    let element = html! { in &bump;
+        <!DOCTYPE html>
+        <html>
+            <body>"Hello"</body>
+        </html>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert!(output.starts_with("<!DOCTYPE html>"));
+    assert_eq!(
+        output,
+        "<!DOCTYPE html><html>\n  <body>Hello</body>\n</html>"
+    );
+}
+
+#[test]
+fn test_for_loop() {
+    let bump = Bump::new();
+    let items = vec!["One", "Two", "Three"];
+
+    let element = html! { in &bump;
+        <ul>
+            @for item in items {
+                <li>{item}</li>
+            }
+        </ul>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert_eq!(
+        output,
+        "<ul>\n  <li>One</li>\n  <li>Two</li>\n  <li>Three</li>\n</ul>"
+    );
+}
+
+#[test]
+fn test_for_loop_with_multiple_body_elements() {
+    let bump = Bump::new();
+    let terms = [("HTML", "Markup"), ("CSS", "Styling")];
+
+    let element = html! { in &bump;
+        <dl>
+            @for (term, definition) in terms.iter() {
+                <dt>{*term}</dt>
+                <dd>{*definition}</dd>
+            }
+        </dl>
+    };
+    let output = Document::new(&bump, [element]).write_to_string().unwrap();
+
+    assert_eq!(
+        output,
+        "<dl>\n  <dt>HTML</dt>\n  <dd>Markup</dd>\n  <dt>CSS</dt>\n  <dd>Styling</dd>\n</dl>"
+    );
+}
+
+#[test]
+fn test_attr_macro() {
+    let bump = Bump::new();
+    let b = Builder::new(&bump);
+    let id = format!("item-{}", 3);
+    let extra = [Attribute::new(&bump, "title", "Extra")];
+
+    let attributes = attr! { in &bump; class="x" id={id} disabled {extra} };
+    let output = Document::new(&bump, [b.tag("button", attributes, false)("Go")])
+        .write_to_string()
+        .unwrap();
+
+    assert_eq!(
+        output,
+        r#"<button class="x" id="item-3" disabled title="Extra">Go</button>"#
+    );
+}
+
+#[test]
+fn test_interpolated_attribute_values() {
+    let bump = Bump::new();
+    let render = |is_disabled: bool| {
+        let count = 3u8;
+        let ratio = 0.5;
+        let element = html! { in &bump;
+            <button disabled={is_disabled} data_count={count} data_ratio={ratio}>"Go"</button>
+        };
+        Document::new(&bump, [element]).write_to_string().unwrap()
+    };
+
+    assert_eq!(
+        render(true),
+        r#"<button disabled data-count="3" data-ratio="0.5">Go</button>"#
+    );
+    assert_eq!(
+        render(false),
+        r#"<button data-count="3" data-ratio="0.5">Go</button>"#
+    );
+}
+
+#[test]
+fn test_optional_attribute_values() {
+    let bump = Bump::new();
+    let render = |title: Option<&str>| {
+        let element = html! { in &bump; <a href="/" title={title}>"Home"</a> };
+        let Element::Tag { attributes, .. } = &element else {
+            panic!("expected tag");
+        };
+        let count = attributes.len();
+        (
+            count,
+            Document::new(&bump, [element]).write_to_string().unwrap(),
+        )
+    };
+
+    assert_eq!(
+        render(Some("Go home")),
+        (2, r#"<a href="/" title="Go home">Home</a>"#.to_string())
+    );
+    assert_eq!(render(None), (1, r#"<a href="/">Home</a>"#.to_string()));
+}
+
+#[test]
+fn test_borrowed_and_owned_attribute_values() {
+    use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+    let bump = Bump::new();
+    let classes = ["primary", "secondary"];
+    let counts = [1, 2];
+    let label: Rc<str> = Rc::from("Label");
+    let element = html! { in &bump;
+        <ul>
+            @for (class, count) in classes.iter().zip(counts.iter()) {
+                <li class={class} data_count={count}>"Item"</li>
+            }
+            <li title={Cow::Borrowed("Cow")} data_key={'k'} data_label={label.clone()}>"Owned"</li>
+            <li title={Box::<str>::from("Box")} data_label={Arc::<str>::from("Arc")}>"Shared"</li>
+        </ul>
+    };
+    let output = Document::new(&bump, [element])
+        .write_compact_to_string()
+        .unwrap();
+
+    assert_eq!(
+        output,
+        concat!(
+            r#"<ul><li class="primary" data-count="1">Item</li>"#,
+            r#"<li class="secondary" data-count="2">Item</li>"#,
+            r#"<li title="Cow" data-key="k" data-label="Label">Owned</li>"#,
+            r#"<li title="Box" data-label="Arc">Shared</li></ul>"#
+        )
+    );
+}
+
+#[test]
+fn test_explicitly_closed_void_elements() {
+    let bump = Bump::new();
+    let element = html! { in &bump;
+        <div><img src="x"></img><br></br>"After"</div>
+    };
+    let output = Document::new(&bump, [element])
+        .write_compact_to_string()
+        .unwrap();
+
+    assert_eq!(output, r#"<div><img src="x"><br>After</div>"#);
+}
@@ -0,0 +1,23 @@
+//! Checks that the crate builds with only `alloc`, as it would for a `no_std` target.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn builds_without_std() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // Use a separate target directory, as the outer build holds a lock on the default one
+    let target_dir = manifest_dir.join("../target/no-std-check");
+    let output = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["check", "--lib", "--no-default-features", "--target-dir"])
+        .arg(&target_dir)
+        .output()
+        .expect("failed to run cargo");
+
+    assert!(
+        output.status.success(),
+        "`cargo check --no-default-features` failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -1,5 +1,5 @@
 use bumpalo::Bump;
-use paxhtml::{parse_html, Document};
+use paxhtml::{parse_html, parse_plain_html, parse_plain_html_lenient, Document};
 
 #[test]
 fn test_runtime_parse_simple_html() {
@@ -115,3 +115,111 @@ fn test_runtime_parse_mixed_attributes() {
         r#"<input type="text" tabindex="0" placeholder="Name">"#
     );
 }
+
+#[test]
+fn test_runtime_parse_bool_and_float_attributes() {
+    let bump = Bump::new();
+    let html = r#"<div hidden=true draggable=false opacity=0.5></div>"#;
+    let element = parse_html(&bump, html).unwrap();
+    let doc = Document::new(&bump, [element]);
+    let output = doc.write_to_string().unwrap();
+
+    assert_eq!(
+        output,
+        r#"<div hidden="true" draggable="false" opacity="0.5"></div>"#
+    );
+}
+
+#[test]
+fn test_runtime_parse_plain_html_text() {
+    let bump = Bump::new();
+    let html = "<div class=\"container\">\n  <p>Hello world</p>\n  <p>it's plain</p>\n</div>";
+    let element = parse_plain_html(&bump, html).unwrap();
+    let doc = Document::new(&bump, [element]);
+    let output = doc.write_to_string().unwrap();
+
+    assert_eq!(
+        output,
+        "<div class=\"container\">\n  <p>Hello world</p>\n  <p>it's plain</p>\n</div>"
+    );
+}
+
+#[test]
+fn test_runtime_parse_plain_html_entity_text() {
+    let bump = Bump::new();
+    let element = parse_plain_html(&bump, "<p>a &amp; b</p>").unwrap();
+
+    assert_eq!(element.inner_text(&bump).as_str(), "a & b");
+}
+
+#[test]
+fn test_runtime_parse_plain_html_entity_round_trip() {
+    let bump = Bump::new();
+    let html = "<p>a &amp; b &lt; c</p>";
+    let element = parse_plain_html(&bump, html).unwrap();
+    let doc = Document::new(&bump, [element]);
+    let output = doc.write_to_string().unwrap();
+
+    assert_eq!(output, html);
+}
+
+#[test]
+fn test_runtime_parse_plain_html_lenient() {
+    let bump = Bump::new();
+    let (element, errors) =
+        parse_plain_html_lenient(&bump, "<div><p>Unclosed <em>emphasis</div><p>Next");
+    let doc = Document::new(&bump, [element]);
+    let output = doc.write_compact_to_string().unwrap();
+
+    assert_eq!(
+        output,
+        "<div><p>Unclosed <em>emphasis</em></p></div><p>Next</p>"
+    );
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0]
+        .to_string()
+        .contains("Mismatched closing tag `</div>` for `<em>`"));
+    assert!(errors[1].to_string().contains("Unclosed tag `<p>`"));
+}
+
+#[test]
+fn test_runtime_parse_void_elements_without_slash() {
+    let bump = Bump::new();
+    let element = parse_html(&bump, r#"<p>"a"<br>"b"<img src="x"></p>"#).unwrap();
+    let doc = Document::new(&bump, [element]);
+    assert_eq!(
+        doc.write_compact_to_string().unwrap(),
+        r#"<p>a<br>b<img src="x"></p>"#
+    );
+
+    let element = parse_plain_html(&bump, "<p>a<br>b<img src=x></p>").unwrap();
+    let doc = Document::new(&bump, [element]);
+    assert_eq!(
+        doc.write_compact_to_string().unwrap(),
+        r#"<p>a<br>b<img src="x"></p>"#
+    );
+}
+
+#[test]
+fn test_typed_literal_attributes_match_macro() {
+    use paxhtml::{html, AttributeValue, Element};
+
+    let bump = Bump::new();
+    let from_macro = html! { in &bump; <meter count=3 ratio=1.5></meter> };
+    let from_runtime = parse_html(&bump, "<meter count=3 ratio=1.5></meter>").unwrap();
+
+    for element in [&from_macro, &from_runtime] {
+        let Element::Tag { attributes, .. } = element else {
+            panic!("Expected tag element");
+        };
+        assert_eq!(attributes[0].value, Some(AttributeValue::Int(3)));
+        assert_eq!(attributes[1].value, Some(AttributeValue::Float(1.5)));
+    }
+    assert_eq!(from_macro, from_runtime);
+    assert_eq!(
+        Document::new(&bump, [from_runtime])
+            .write_to_string()
+            .unwrap(),
+        r#"<meter count="3" ratio="1.5"></meter>"#
+    );
+}
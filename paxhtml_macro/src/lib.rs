@@ -1,4 +1,4 @@
-use paxhtml_parser::{AstAttribute, AstNode, AttributeValue, SynAstNode};
+use paxhtml_parser::{AstAttribute, AstNode, AttributeValue, SynAstAttributes, SynAstNode};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens};
@@ -28,6 +28,26 @@ impl Parse for HtmlInput {
     }
 }
 
+/// Input format: `in <allocator>; <attributes>`
+struct AttrInput {
+    allocator: Expr,
+    attributes: SynAstAttributes,
+}
+impl Parse for AttrInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![in]>()?;
+        let allocator = input.parse::<Expr>()?;
+        input.parse::<Token![;]>()?;
+
+        let attributes = input.parse::<SynAstAttributes>()?;
+
+        Ok(AttrInput {
+            allocator,
+            attributes,
+        })
+    }
+}
+
 // Wrapper to allow code generation with bump allocator
 struct AstNodeWithBump<'a> {
     bump: &'a Expr,
@@ -55,7 +75,20 @@ fn ast_node_to_tokens_with_bump(bump: &Expr, node: &AstNode, tokens: &mut TokenS
                     .any(|attr| matches!(attr, AstAttribute::Interpolated(_)));
                 if has_interpolated {
                     tokens.extend(quote! {
-                        compile_error!("Interpolated attributes are not supported for custom components")
+                        compile_error!("Interpolated attributes are not supported for custom components; use `{..props}` to spread props instead")
+                    });
+                    return;
+                }
+
+                // A spread provides the base props; only one is permitted
+                let mut spreads = attributes.iter().filter_map(|attr| match attr {
+                    AstAttribute::Spread(expr) => Some(expr),
+                    _ => None,
+                });
+                let spread = spreads.next();
+                if spreads.next().is_some() {
+                    tokens.extend(quote! {
+                        compile_error!("Only one spread is supported for custom components")
                     });
                     return;
                 }
@@ -87,103 +120,89 @@ fn ast_node_to_tokens_with_bump(bump: &Expr, node: &AstNode, tokens: &mut TokenS
                     }
                 }
 
-                // Add children if present (as Option<Element> using from_iter)
-                if !children.is_empty() {
-                    let children_tokens: Vec<_> = children
+                // Route `<slot name="...">` children to the correspondingly-named field
+                let mut slots: Vec<(syn::Ident, &[AstNode])> = Vec::new();
+                let mut unnamed_children = Vec::new();
+                for child in children {
+                    match slot_name(child) {
+                        Some(Ok((slot, slot_children))) => {
+                            if slots.iter().any(|(existing, _)| *existing == slot) {
+                                let message = format!("Slot `{slot}` is specified more than once");
+                                tokens.extend(quote! { compile_error!(#message) });
+                                return;
+                            }
+                            slots.push((slot, slot_children));
+                        }
+                        Some(Err(message)) => {
+                            tokens.extend(quote! { compile_error!(#message) });
+                            return;
+                        }
+                        None => unnamed_children.push(child),
+                    }
+                }
+                for (field_ident, slot_children) in slots {
+                    let children_tokens: Vec<_> = slot_children
                         .iter()
                         .map(|c| AstNodeWithBump { bump, node: c })
                         .collect();
                     field_inits.push(quote! {
-                        children: Some(paxhtml::Element::from_iter(#bump, [#(#children_tokens),*]))
+                        #field_ident: paxhtml::FromChildren::from_children(
+                            #bump,
+                            paxhtml::Element::from_iter(#bump, [#(#children_tokens),*])
+                        )
+                    });
+                }
+
+                // Add remaining children if present, converted to the type of the `children` field
+                if !unnamed_children.is_empty() {
+                    let children_tokens: Vec<_> = unnamed_children
+                        .into_iter()
+                        .map(|c| AstNodeWithBump { bump, node: c })
+                        .collect();
+                    field_inits.push(quote! {
+                        children: paxhtml::FromChildren::from_children(
+                            #bump,
+                            paxhtml::Element::from_iter(#bump, [#(#children_tokens),*])
+                        )
                     });
                 }
 
-                // Use struct update syntax with default_in for unspecified fields
+                // Use struct update syntax for unspecified fields, taking them from the
+                // spread if present and from default_in otherwise
+                let base = match spread {
+                    Some(expr) => quote! { #expr },
+                    None => quote! { paxhtml::DefaultIn::default_in(#bump) },
+                };
                 tokens.extend(quote! {
                     #component_ident(#bump, {
                         #[allow(clippy::needless_update)]
                         #props_ident {
                             #(#field_inits,)*
-                            ..paxhtml::DefaultIn::default_in(#bump)
+                            ..#base
                         }
                     })
                 });
             } else {
                 // Regular HTML element
-                let attrs_code = if attributes.is_empty() {
-                    quote! { paxhtml::bumpalo::collections::Vec::new_in(#bump) }
-                } else {
-                    let mut attr_statements = Vec::new();
-                    for attr in attributes {
-                        match attr {
-                            AstAttribute::Named { name, value } => {
-                                let attr_statement = match value {
-                                    Some(AttributeValue::Expression(expr)) => quote! {
-                                        __attrs.push(paxhtml::Attribute::new(
-                                            #bump,
-                                            #name,
-                                            &(#expr).to_string()
-                                        ));
-                                    },
-                                    Some(AttributeValue::LiteralString(s)) => quote! {
-                                        __attrs.push(paxhtml::Attribute::new(#bump, #name, #s));
-                                    },
-                                    Some(AttributeValue::LiteralInt(i)) => quote! {
-                                        __attrs.push(paxhtml::Attribute::new_int(#bump, #name, #i));
-                                    },
-                                    Some(AttributeValue::LiteralFloat(f)) => quote! {
-                                        __attrs.push(paxhtml::Attribute::new_float(#bump, #name, #f));
-                                    },
-                                    Some(AttributeValue::LiteralBool(b)) => quote! {
-                                        __attrs.push(paxhtml::Attribute::new_bool(#bump, #name, #b));
-                                    },
-                                    None => quote! {
-                                        __attrs.push(paxhtml::Attribute::boolean(#bump, #name));
-                                    },
-                                };
-                                attr_statements.push(attr_statement);
-                            }
-                            AstAttribute::Interpolated(expr) => {
-                                attr_statements.push(quote! {
-                                    for __a in #expr {
-                                        __attrs.push(__a);
-                                    }
-                                });
-                            }
-                        }
-                    }
-                    quote! {{
-                        let mut __attrs = paxhtml::bumpalo::collections::Vec::new_in(#bump);
-                        #(#attr_statements)*
-                        __attrs
-                    }}
-                };
-
-                let children_code = if children.is_empty() {
-                    quote! { paxhtml::bumpalo::collections::Vec::new_in(#bump) }
-                } else {
-                    let children_tokens: Vec<_> = children
-                        .iter()
-                        .map(|c| AstNodeWithBump { bump, node: c })
-                        .collect();
-                    quote! {{
-                        let mut __children = paxhtml::bumpalo::collections::Vec::new_in(#bump);
-                        #(__children.push(#children_tokens);)*
-                        __children
-                    }}
-                };
-
                 let name_str = name.as_str();
-                tokens.extend(quote! {
-                    paxhtml::Element::Tag {
-                        name: paxhtml::bumpalo::collections::String::from_str_in(#name_str, #bump),
-                        attributes: #attrs_code,
-                        children: #children_code,
-                        void: #void,
-                    }
-                });
+                tag_to_tokens(
+                    bump,
+                    quote! { #name_str },
+                    attributes,
+                    children,
+                    *void,
+                    tokens,
+                );
             }
         }
+        AstNode::DynamicElement {
+            name,
+            attributes,
+            children,
+            void,
+        } => {
+            tag_to_tokens(bump, quote! { #name }, attributes, children, *void, tokens);
+        }
         AstNode::Fragment(children) => {
             let children_tokens: Vec<_> = children
                 .iter()
@@ -206,6 +225,24 @@ fn ast_node_to_tokens_with_bump(bump: &Expr, node: &AstNode, tokens: &mut TokenS
                 });
             }
         }
+        AstNode::For {
+            pattern,
+            iterable,
+            body,
+        } => {
+            let body_tokens: Vec<_> = body
+                .iter()
+                .map(|c| AstNodeWithBump { bump, node: c })
+                .collect();
+            tokens.extend(quote! {
+                paxhtml::Element::from_iter(
+                    #bump,
+                    ::core::iter::IntoIterator::into_iter(#iterable).map(|#pattern| {
+                        paxhtml::Element::from_iter(#bump, [#(#body_tokens),*])
+                    })
+                )
+            });
+        }
         AstNode::Text(text) => {
             tokens.extend(quote! {
                 paxhtml::Element::Text {
@@ -213,6 +250,158 @@ fn ast_node_to_tokens_with_bump(bump: &Expr, node: &AstNode, tokens: &mut TokenS
                 }
             });
         }
+        AstNode::Raw(html) => {
+            tokens.extend(quote! {
+                paxhtml::Element::Raw {
+                    html: paxhtml::bumpalo::collections::String::from_str_in(#html, #bump)
+                }
+            });
+        }
+    }
+}
+
+// If `node` is a `<slot name="...">` element, returns the field it fills and its children.
+// Only used for the direct children of a custom component invocation
+fn slot_name(node: &AstNode) -> Option<Result<(syn::Ident, &[AstNode]), String>> {
+    let AstNode::Element {
+        name,
+        attributes,
+        children,
+        ..
+    } = node
+    else {
+        return None;
+    };
+    if name != "slot" {
+        return None;
+    }
+
+    let mut slot_name = None;
+    for attr in attributes {
+        match attr {
+            AstAttribute::Named {
+                name,
+                value: Some(AttributeValue::LiteralString(value)),
+            } if name == "name" => slot_name = Some(value.clone()),
+            _ => {
+                return Some(Err(
+                    "Slots in custom components only support a literal `name` attribute"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+    let Some(slot_name) = slot_name else {
+        return Some(Err(
+            "Slots in custom components require a `name` attribute".to_string()
+        ));
+    };
+    Some(
+        syn::parse_str::<syn::Ident>(&slot_name.replace('-', "_"))
+            .map(|field_ident| (field_ident, children.as_slice()))
+            .map_err(|_| format!("Slot name `{slot_name}` is not a valid field name")),
+    )
+}
+
+// Generates an `Element::Tag` whose name is given by `name`, which must evaluate to a `&str`
+fn tag_to_tokens(
+    bump: &Expr,
+    name: TokenStream2,
+    attributes: &[AstAttribute],
+    children: &[AstNode],
+    void: bool,
+    tokens: &mut TokenStream2,
+) {
+    let attrs_code = attributes_to_tokens(bump, attributes);
+
+    let children_code = if children.is_empty() {
+        quote! { paxhtml::bumpalo::collections::Vec::new_in(#bump) }
+    } else {
+        let children_tokens: Vec<_> = children
+            .iter()
+            .map(|c| AstNodeWithBump { bump, node: c })
+            .collect();
+        quote! {{
+            let mut __children = paxhtml::bumpalo::collections::Vec::new_in(#bump);
+            #(__children.push(#children_tokens);)*
+            __children
+        }}
+    };
+
+    tokens.extend(quote! {
+        paxhtml::Element::Tag {
+            name: paxhtml::bumpalo::collections::String::from_str_in(#name, #bump),
+            attributes: #attrs_code,
+            children: #children_code,
+            void: #void,
+            key: None,
+        }
+    });
+}
+
+// Generates a `BumpVec<Attribute>` containing the given attributes
+fn attributes_to_tokens(bump: &Expr, attributes: &[AstAttribute]) -> TokenStream2 {
+    if attributes.is_empty() {
+        quote! { paxhtml::bumpalo::collections::Vec::new_in(#bump) }
+    } else {
+        // Named attributes take precedence over spread attributes with the same key
+        let named_keys: Vec<_> = attributes
+            .iter()
+            .filter_map(|attr| match attr {
+                AstAttribute::Named { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        let mut attr_statements = Vec::new();
+        for attr in attributes {
+            match attr {
+                AstAttribute::Named { name, value } => {
+                    let attr_statement = match value {
+                        Some(AttributeValue::Expression(expr)) => quote! {
+                            __attrs.extend(paxhtml::Attribute::from_value(#bump, #name, #expr));
+                        },
+                        Some(AttributeValue::LiteralString(s)) => quote! {
+                            __attrs.push(paxhtml::Attribute::new(#bump, #name, #s));
+                        },
+                        Some(AttributeValue::LiteralInt(i)) => quote! {
+                            __attrs.push(paxhtml::Attribute::new_int(#bump, #name, #i));
+                        },
+                        Some(AttributeValue::LiteralFloat(f)) => quote! {
+                            __attrs.push(paxhtml::Attribute::new_float(#bump, #name, #f));
+                        },
+                        Some(AttributeValue::LiteralBool(b)) => quote! {
+                            __attrs.push(paxhtml::Attribute::new_bool(#bump, #name, #b));
+                        },
+                        None => quote! {
+                            __attrs.push(paxhtml::Attribute::boolean(#bump, #name));
+                        },
+                    };
+                    attr_statements.push(attr_statement);
+                }
+                AstAttribute::Interpolated(expr) => {
+                    attr_statements.push(quote! {
+                        for __a in #expr {
+                            __attrs.push(__a);
+                        }
+                    });
+                }
+                AstAttribute::Spread(expr) => {
+                    attr_statements.push(quote! {
+                        for __a in #expr {
+                            if ![#(#named_keys),*].contains(&__a.key.as_str()) {
+                                __attrs.push(__a);
+                            }
+                        }
+                    });
+                }
+            }
+        }
+        quote! {{
+            let mut __attrs = paxhtml::bumpalo::collections::Vec::new_in(#bump);
+            #(#attr_statements)*
+            __attrs
+        }}
     }
 }
 
@@ -230,8 +419,22 @@ fn ast_node_to_tokens_with_bump(bump: &Expr, node: &AstNode, tokens: &mut TokenS
 ///
 /// Interpolation is supported using `{}` for expressions and `#{...}` for iterators.
 ///
+/// Loops are supported using `@for pattern in expr { ... }`, which produces the body for
+/// each item of `expr`.
+///
+/// Attributes can be spread using `{..expr}`. On regular elements, `expr` must be an
+/// iterable of [`paxhtml::Attribute`]s; any named attribute on the element overrides a
+/// spread attribute with the same key, regardless of order. On custom components, `expr`
+/// must evaluate to the props struct, and named attributes override its fields.
+///
 /// Fragments are supported using `<>...</>` syntax.
 ///
+/// A `<!DOCTYPE html>` declaration may precede the root element, in which case a fragment
+/// containing both is produced.
+///
+/// Tag names can be computed at runtime using `<{expr}>...</{expr}>`, where `expr` evaluates
+/// to a `&str`. The closing tag may also be written as `</>`.
+///
 /// # Example
 ///
 /// ```ignore
@@ -254,3 +457,155 @@ pub fn html(input: TokenStream) -> TokenStream {
 
     quote! { #wrapper }.into()
 }
+
+#[proc_macro]
+/// Constructs a list of [`paxhtml::Attribute`]s using the attribute syntax of [`html!`].
+///
+/// # Syntax
+///
+/// ```ignore
+/// attr! { in <allocator>; <attributes>... }
+/// ```
+///
+/// This evaluates to a `bumpalo::collections::Vec` of attributes, which can be passed to
+/// any builder method. Values can be literals or `{expr}`, valueless attributes are
+/// boolean, and `{expr}` on its own interpolates an iterable of attributes.
+///
+/// # Example
+///
+/// ```ignore
+/// use paxhtml::{attr, builder::Builder, Bump};
+///
+/// let bump = Bump::new();
+/// let b = Builder::new(&bump);
+/// let id = "main";
+/// let element = b.div(attr! { in &bump; class="container" id={id} hidden })([]);
+/// ```
+pub fn attr(input: TokenStream) -> TokenStream {
+    let AttrInput {
+        allocator,
+        attributes,
+    } = syn::parse_macro_input!(input as AttrInput);
+
+    attributes_to_tokens(&allocator, &attributes.0).into()
+}
+
+#[proc_macro_derive(DefaultIn)]
+/// Derives [`paxhtml::DefaultIn`] for a struct with named fields, such as the props of a
+/// custom component.
+///
+/// A field named `children` whose type is a `BumpVec` (or `bumpalo::collections::Vec`) is
+/// created empty in the bump allocator, and every other field is created with
+/// [`Default::default`]. The struct's first lifetime parameter, if any, is used as the
+/// lifetime of the allocator.
+///
+/// # Example
+///
+/// ```ignore
+/// use paxhtml::{bumpalo::collections::Vec as BumpVec, DefaultIn, Element};
+///
+/// #[derive(DefaultIn)]
+/// struct CardProps<'bump> {
+///     title: String,
+///     children: BumpVec<'bump, Element<'bump>>,
+/// }
+/// ```
+pub fn derive_default_in(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match default_in_to_tokens(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn default_in_to_tokens(input: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "DefaultIn can only be derived for structs with named fields",
+        ));
+    };
+
+    let field_values = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        if ident == "children" && is_bump_vec(&field.ty) {
+            quote! { #ident: paxhtml::bumpalo::collections::Vec::new_in(bump) }
+        } else {
+            quote! { #ident: ::core::default::Default::default() }
+        }
+    });
+
+    // Use the struct's own lifetime for the allocator if it has one, and a fresh one otherwise
+    let mut impl_generics = input.generics.clone();
+    let bump_lifetime = match input.generics.lifetimes().next() {
+        Some(lifetime) => lifetime.lifetime.clone(),
+        None => {
+            let lifetime = syn::Lifetime::new("'__bump", proc_macro2::Span::call_site());
+            impl_generics.params.insert(
+                0,
+                syn::GenericParam::Lifetime(syn::LifetimeParam::new(lifetime.clone())),
+            );
+            lifetime
+        }
+    };
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, type_generics, where_clause) = input.generics.split_for_impl();
+    let name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics paxhtml::DefaultIn<#bump_lifetime> for #name #type_generics #where_clause {
+            fn default_in(bump: &#bump_lifetime paxhtml::bumpalo::Bump) -> Self {
+                let _ = bump;
+                Self {
+                    #(#field_values),*
+                }
+            }
+        }
+    })
+}
+
+// Whether a type is a path ending in `BumpVec`, or in `Vec` from `bumpalo`
+fn is_bump_vec(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    let segments = &path.path.segments;
+    segments.last().is_some_and(|last| {
+        last.ident == "BumpVec"
+            || (last.ident == "Vec" && segments.iter().any(|s| s.ident == "bumpalo"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(name: &str) -> AstNode {
+        AstNode::Element {
+            name: "slot".to_string(),
+            attributes: vec![AstAttribute::Named {
+                name: "name".to_string(),
+                value: Some(AttributeValue::LiteralString(name.to_string())),
+            }],
+            children: vec![],
+            void: false,
+        }
+    }
+
+    #[test]
+    fn slot_names_must_be_valid_field_names() {
+        let (field_ident, _) = slot_name(&slot("page-header")).unwrap().unwrap();
+        assert_eq!(field_ident, "page_header");
+
+        for name in ["", "1st", "two words", "type", "a.b"] {
+            assert_eq!(
+                slot_name(&slot(name)).unwrap().unwrap_err(),
+                format!("Slot name `{name}` is not a valid field name")
+            );
+        }
+    }
+}
@@ -1,5 +1,36 @@
 use proc_macro2::TokenStream;
 
+/// A range of bytes in the source, from `start` (inclusive) to `end` (exclusive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// The byte offset of the start of the range.
+    pub start: usize,
+    /// The byte offset just past the end of the range.
+    pub end: usize,
+}
+
+/// The source [`Span`]s of an [`AstNode`] and its descendants, as returned by
+/// [`crate::parse_plain_html_spanned`].
+///
+/// This is kept separate from [`AstNode`] so that spans are only computed when needed.
+/// `children` mirrors the children of the corresponding [`AstNode::Element`] or
+/// [`AstNode::Fragment`], and is empty for other nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstNodeSpanned {
+    /// The span of the node.
+    pub span: Span,
+    /// The spans of the node's children, in order.
+    pub children: Vec<AstNodeSpanned>,
+}
+impl AstNodeSpanned {
+    pub(crate) fn leaf(span: Span) -> Self {
+        Self {
+            span,
+            children: vec![],
+        }
+    }
+}
+
 /// Represents an HTML attribute in the AST
 #[derive(Debug, Clone)]
 pub enum AstAttribute {
@@ -10,6 +41,12 @@ pub enum AstAttribute {
     },
     /// An interpolated attribute expression (macro only)
     Interpolated(TokenStream),
+    /// A spread attribute expression, written as `{..expr}` (macro only)
+    ///
+    /// On regular elements, this yields attributes that are overridden by any named
+    /// attribute with the same key. On custom components, this provides the base props
+    /// that named attributes override.
+    Spread(TokenStream),
 }
 
 /// Represents an attribute value
@@ -52,12 +89,28 @@ pub enum AstNode {
         children: Vec<AstNode>,
         void: bool,
     },
+    /// An HTML element whose tag name is computed from an expression (macro only)
+    DynamicElement {
+        name: TokenStream,
+        attributes: Vec<AstAttribute>,
+        children: Vec<AstNode>,
+        void: bool,
+    },
     /// A fragment containing multiple children without a wrapper element
     Fragment(Vec<AstNode>),
     /// An interpolated expression (macro only)
     Expression { body: TokenStream, iterator: bool },
+    /// A loop over an iterable, written as `@for pattern in expr { body }` (macro only)
+    For {
+        pattern: TokenStream,
+        iterable: TokenStream,
+        body: Vec<AstNode>,
+    },
     /// Text content
     Text(String),
+    /// Raw HTML content that should not be escaped, such as the contents of a `<script>`
+    /// (plain HTML parsing only)
+    Raw(String),
 }
 
 impl AstNode {
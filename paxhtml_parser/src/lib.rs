@@ -1,5 +1,11 @@
 mod ast;
 mod parser;
+mod plain;
 
-pub use ast::{AstAttribute, AstNode, AttributeValue};
-pub use parser::{parse_html, ParseError, SynAstNode};
+pub use ast::{AstAttribute, AstNode, AstNodeSpanned, AttributeValue, Span};
+pub use parser::{
+    parse_html, parse_html_fragment, ParseError, SynAstAttributes, SynAstFragment, SynAstNode,
+};
+pub use plain::{
+    parse_plain_html, parse_plain_html_lenient, parse_plain_html_spanned, tokenize, Token,
+};
@@ -3,7 +3,7 @@ use convert_case::{Case, Casing};
 use std::fmt;
 use syn::{
     parse::{Parse, ParseStream},
-    token, Expr, Ident, Lit, LitStr, Result as SynResult, Token,
+    token, Expr, Ident, Lit, LitStr, Pat, Result as SynResult, Token,
 };
 
 /// Error type for parsing HTML
@@ -33,15 +33,55 @@ pub struct SynAstNode(pub AstNode);
 
 impl Parse for SynAstNode {
     fn parse(input: ParseStream) -> SynResult<Self> {
-        Ok(SynAstNode(parse_node(input)?))
+        let node = parse_node(input)?;
+        if node.element_name() == Some("!DOCTYPE") && !input.is_empty() {
+            // A doctype is followed by the root element of the document
+            let root = parse_node(input)?;
+            return Ok(SynAstNode(AstNode::Fragment(vec![node, root])));
+        }
+        Ok(SynAstNode(node))
+    }
+}
+
+/// A sequence of nodes, parsed until the end of the input into an [`AstNode::Fragment`].
+#[derive(Debug)]
+pub struct SynAstFragment(pub AstNode);
+
+impl Parse for SynAstFragment {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        let mut nodes = Vec::new();
+        while !input.is_empty() {
+            nodes.push(parse_node(input)?);
+        }
+        Ok(SynAstFragment(AstNode::Fragment(nodes)))
+    }
+}
+
+/// A list of attributes, parsed until the end of the input.
+///
+/// This uses the same grammar as the attributes of an element parsed by [`SynAstNode`].
+pub struct SynAstAttributes(pub Vec<AstAttribute>);
+
+impl Parse for SynAstAttributes {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        let mut attributes = Vec::new();
+        while !input.is_empty() {
+            attributes.push(parse_attribute(input)?);
+        }
+        Ok(SynAstAttributes(attributes))
     }
 }
 
 fn parse_attribute(input: ParseStream) -> SynResult<AstAttribute> {
     if input.peek(token::Brace) {
-        // Parse interpolated attribute
+        // Parse interpolated or spread attribute
         let content;
         syn::braced!(content in input);
+        if content.peek(Token![..]) {
+            content.parse::<Token![..]>()?;
+            let expr = content.parse::<Expr>()?;
+            return Ok(AstAttribute::Spread(quote::quote! { #expr }));
+        }
         let expr = content.parse::<Expr>()?;
         Ok(AstAttribute::Interpolated(quote::quote! { #expr }))
     } else {
@@ -93,30 +133,71 @@ fn parse_attribute(input: ParseStream) -> SynResult<AstAttribute> {
     }
 }
 
+fn parse_doctype(input: ParseStream) -> SynResult<AstNode> {
+    input.parse::<Token![<]>()?;
+    input.parse::<Token![!]>()?;
+    let keyword = input.parse::<Ident>()?;
+    if !keyword.to_string().eq_ignore_ascii_case("doctype") {
+        return Err(syn::Error::new(keyword.span(), "Expected `DOCTYPE`"));
+    }
+
+    let mut attributes = Vec::new();
+    while !input.peek(Token![>]) {
+        attributes.push(parse_attribute(input)?);
+    }
+    input.parse::<Token![>]>()?;
+
+    Ok(AstNode::Element {
+        name: "!DOCTYPE".to_string(),
+        attributes,
+        children: vec![],
+        void: true,
+    })
+}
+
+fn parse_for(input: ParseStream) -> SynResult<AstNode> {
+    input.parse::<Token![@]>()?;
+    input.parse::<Token![for]>()?;
+    let pattern = Pat::parse_single(input)?;
+    input.parse::<Token![in]>()?;
+    let iterable = Expr::parse_without_eager_brace(input)?;
+
+    let content;
+    syn::braced!(content in input);
+    let mut body = Vec::new();
+    while !content.is_empty() {
+        body.push(parse_node(&content)?);
+    }
+
+    Ok(AstNode::For {
+        pattern: quote::quote! { #pattern },
+        iterable: quote::quote! { #iterable },
+        body,
+    })
+}
+
 fn parse_node(input: ParseStream) -> SynResult<AstNode> {
-    if input.peek(token::Lt) {
+    if input.peek(token::Lt) && input.peek2(Token![!]) {
+        parse_doctype(input)
+    } else if input.peek(Token![@]) {
+        parse_for(input)
+    } else if input.peek(token::Lt) {
         // Parse element
         input.parse::<Token![<]>()?;
 
         enum TagType {
             Fragment,
             Name(String),
-        }
-
-        impl TagType {
-            pub fn is_fragment(&self) -> bool {
-                matches!(self, TagType::Fragment)
-            }
-            pub fn unwrap_name_as_ref(&self) -> &str {
-                match self {
-                    TagType::Name(name) => name,
-                    TagType::Fragment => panic!("Fragment cannot have a name"),
-                }
-            }
+            Dynamic(proc_macro2::TokenStream),
         }
 
         let tag = if input.peek(Token![>]) {
             TagType::Fragment
+        } else if input.peek(token::Brace) {
+            let content;
+            syn::braced!(content in input);
+            let expr = content.parse::<Expr>()?;
+            TagType::Dynamic(quote::quote! { #expr })
         } else {
             let name = input.parse::<Ident>()?.to_string();
             TagType::Name(name.strip_prefix("r#").unwrap_or(&name).to_string())
@@ -128,14 +209,32 @@ fn parse_node(input: ParseStream) -> SynResult<AstNode> {
             attributes.push(parse_attribute(input)?);
         }
 
-        // Handle void elements
+        // Handle void elements, which are either self-closed or known to be void
         let void = if input.peek(Token![/]) {
             input.parse::<Token![/]>()?;
             input.parse::<Token![>]>()?;
             true
         } else {
             input.parse::<Token![>]>()?;
-            false
+            match &tag {
+                TagType::Name(name) if crate::plain::is_void_element(name) => {
+                    // Known void tags may still be explicitly closed, e.g. `<img></img>`
+                    let fork = input.fork();
+                    let closes_tag = fork.parse::<Token![<]>().is_ok()
+                        && fork.parse::<Token![/]>().is_ok()
+                        && fork
+                            .parse::<Ident>()
+                            .is_ok_and(|close_name| close_name == name.as_str());
+                    if closes_tag {
+                        input.parse::<Token![<]>()?;
+                        input.parse::<Token![/]>()?;
+                        input.parse::<Ident>()?;
+                        input.parse::<Token![>]>()?;
+                    }
+                    true
+                }
+                _ => false,
+            }
         };
 
         if void {
@@ -148,7 +247,15 @@ fn parse_node(input: ParseStream) -> SynResult<AstNode> {
                         void: true,
                     });
                 }
-                _ => return Err(input.error("Fragment cannot be void")),
+                TagType::Dynamic(name) => {
+                    return Ok(AstNode::DynamicElement {
+                        name,
+                        attributes,
+                        children: vec![],
+                        void: true,
+                    });
+                }
+                TagType::Fragment => return Err(input.error("Fragment cannot be void")),
             }
         }
 
@@ -170,8 +277,8 @@ fn parse_node(input: ParseStream) -> SynResult<AstNode> {
                     body: quote::quote! { #expr },
                     iterator,
                 });
-            } else if input.peek(Token![<]) {
-                // Parse nested element
+            } else if input.peek(Token![<]) || input.peek(Token![@]) {
+                // Parse nested element or loop
                 children.push(parse_node(input)?);
             } else {
                 // Parse text content
@@ -187,10 +294,24 @@ fn parse_node(input: ParseStream) -> SynResult<AstNode> {
         // Parse closing tag
         input.parse::<Token![<]>()?;
         input.parse::<Token![/]>()?;
-        if !tag.is_fragment() {
-            let close_name = input.parse::<Ident>()?.to_string();
-            if close_name != tag.unwrap_name_as_ref() {
-                return Err(input.error("Mismatched opening and closing tags"));
+        match &tag {
+            TagType::Fragment => {}
+            TagType::Name(name) => {
+                let close_name = input.parse::<Ident>()?.to_string();
+                if &close_name != name {
+                    return Err(input.error("Mismatched opening and closing tags"));
+                }
+            }
+            TagType::Dynamic(name) => {
+                // Dynamic tags can be closed with either the same expression or `</>`
+                if input.peek(token::Brace) {
+                    let content;
+                    syn::braced!(content in input);
+                    let expr = content.parse::<Expr>()?;
+                    if quote::quote! { #expr }.to_string() != name.to_string() {
+                        return Err(input.error("Mismatched opening and closing tags"));
+                    }
+                }
             }
         }
         input.parse::<Token![>]>()?;
@@ -203,6 +324,12 @@ fn parse_node(input: ParseStream) -> SynResult<AstNode> {
                 children,
                 void: false,
             }),
+            TagType::Dynamic(name) => Ok(AstNode::DynamicElement {
+                name,
+                attributes,
+                children,
+                void: false,
+            }),
         }
     } else if input.peek(token::Brace) || (input.peek(Token![#]) && input.peek2(token::Brace)) {
         // Parse interpolated Rust expression
@@ -231,6 +358,13 @@ pub fn parse_html(html: &str) -> Result<AstNode, ParseError> {
     Ok(node.0)
 }
 
+/// Parse an HTML string containing any number of top-level nodes into an
+/// [`AstNode::Fragment`] using syn::parse_str
+pub fn parse_html_fragment(html: &str) -> Result<AstNode, ParseError> {
+    let fragment: SynAstFragment = syn::parse_str(html)?;
+    Ok(fragment.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,4 +582,190 @@ mod tests {
             _ => panic!("Expected element"),
         }
     }
+
+    #[test]
+    fn test_parse_spread_attribute() {
+        let html = r#"<div {..extra} id="main"></div>"#;
+        let result = parse_html(html).unwrap();
+
+        match result {
+            AstNode::Element { attributes, .. } => {
+                assert_eq!(attributes.len(), 2);
+                assert!(matches!(&attributes[0], AstAttribute::Spread(_)));
+                assert!(matches!(&attributes[1], AstAttribute::Named { .. }));
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_dynamic_element() {
+        let html = r#"<{tag} class="x">"Hello"</{tag}>"#;
+        let result = parse_html(html).unwrap();
+
+        match result {
+            AstNode::DynamicElement {
+                name,
+                attributes,
+                children,
+                void,
+            } => {
+                assert_eq!(name.to_string(), "tag");
+                assert_eq!(attributes.len(), 1);
+                assert_eq!(children.len(), 1);
+                assert!(!void);
+            }
+            _ => panic!("Expected dynamic element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_dynamic_element_with_empty_close() {
+        let html = r#"<{tag}>"Hello"</>"#;
+        let result = parse_html(html).unwrap();
+        assert!(matches!(result, AstNode::DynamicElement { .. }));
+    }
+
+    #[test]
+    fn test_parse_dynamic_element_mismatched_close() {
+        let html = r#"<{tag}>"Hello"</{other}>"#;
+        assert!(parse_html(html).is_err());
+    }
+
+    #[test]
+    fn test_parse_doctype_followed_by_root() {
+        let html = r#"<!DOCTYPE html><html><body>"Hello"</body></html>"#;
+        let result = parse_html(html).unwrap();
+
+        match result {
+            AstNode::Fragment(children) => {
+                assert_eq!(children.len(), 2);
+                match &children[0] {
+                    AstNode::Element {
+                        name,
+                        attributes,
+                        void,
+                        ..
+                    } => {
+                        assert_eq!(name, "!DOCTYPE");
+                        assert_eq!(attributes.len(), 1);
+                        assert!(void);
+                    }
+                    _ => panic!("Expected doctype element"),
+                }
+                assert_eq!(children[1].element_name(), Some("html"));
+            }
+            _ => panic!("Expected fragment"),
+        }
+    }
+
+    #[test]
+    fn test_parse_for_loop() {
+        let html = r#"<ul>@for item in items { <li>{item}</li> }</ul>"#;
+        let result = parse_html(html).unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => {
+                assert_eq!(children.len(), 1);
+                match &children[0] {
+                    AstNode::For {
+                        pattern,
+                        iterable,
+                        body,
+                    } => {
+                        assert_eq!(pattern.to_string(), "item");
+                        assert_eq!(iterable.to_string(), "items");
+                        assert_eq!(body.len(), 1);
+                    }
+                    _ => panic!("Expected for loop"),
+                }
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_attribute_list() {
+        let SynAstAttributes(attributes) =
+            syn::parse_str(r#"class="x" id={dynamic} disabled data_count=3 {extra}"#).unwrap();
+
+        assert_eq!(attributes.len(), 5);
+        assert!(matches!(
+            &attributes[0],
+            AstAttribute::Named { name, value: Some(AttributeValue::LiteralString(s)) }
+                if name == "class" && s == "x"
+        ));
+        assert!(matches!(
+            &attributes[1],
+            AstAttribute::Named { name, value: Some(AttributeValue::Expression(_)) } if name == "id"
+        ));
+        assert!(matches!(
+            &attributes[2],
+            AstAttribute::Named { name, value: None } if name == "disabled"
+        ));
+        assert!(matches!(
+            &attributes[3],
+            AstAttribute::Named { name, value: Some(AttributeValue::LiteralInt(3)) }
+                if name == "data-count"
+        ));
+        assert!(matches!(&attributes[4], AstAttribute::Interpolated(_)));
+    }
+
+    #[test]
+    fn test_parse_known_void_element_without_slash() {
+        let result = parse_html(r#"<p>"a"<br>"b"<img src="x"></p>"#).unwrap();
+
+        let AstNode::Element { children, .. } = result else {
+            panic!("Expected element");
+        };
+        assert_eq!(children.len(), 4);
+        for (child, expected) in [(&children[1], "br"), (&children[3], "img")] {
+            match child {
+                AstNode::Element {
+                    name,
+                    children,
+                    void,
+                    ..
+                } => {
+                    assert_eq!(name, expected);
+                    assert!(children.is_empty());
+                    assert!(void);
+                }
+                _ => panic!("Expected element"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_known_void_element_with_closing_tag() {
+        for html in [r#"<div><img src="x"></img></div>"#, "<div><br></br></div>"] {
+            let AstNode::Element { children, .. } = parse_html(html).unwrap() else {
+                panic!("Expected element");
+            };
+            assert_eq!(children.len(), 1);
+            assert!(matches!(
+                &children[0],
+                AstNode::Element { children, void: true, .. } if children.is_empty()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_fragment_with_siblings() {
+        let names = |html| match parse_html_fragment(html).unwrap() {
+            AstNode::Fragment(children) => children
+                .iter()
+                .map(|child| child.element_name().unwrap().to_string())
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected fragment"),
+        };
+
+        assert_eq!(names(r#"<p>"a"</p><p>"b"</p>"#), ["p", "p"]);
+        assert_eq!(
+            names(r#"<h1>"Title"</h1><p>"a"</p><hr />"#),
+            ["h1", "p", "hr"]
+        );
+        assert_eq!(names(""), Vec::<String>::new());
+        assert!(parse_html(r#"<p>"a"</p><p>"b"</p>"#).is_err());
+    }
 }
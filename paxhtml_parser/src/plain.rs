@@ -0,0 +1,929 @@
+//! A parser for plain HTML, as opposed to the `syn`-based macro grammar.
+//!
+//! Text content does not need to be quoted, and attribute names are taken verbatim.
+//! Character references (`&amp;`, `&#39;`) in text and attribute values are decoded.
+
+use crate::ast::{AstAttribute, AstNode, AstNodeSpanned, AttributeValue, Span};
+use crate::parser::ParseError;
+
+/// Elements that never have children or a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is emitted verbatim, without parsing tags.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Elements whose content is text, without parsing tags.
+const ESCAPABLE_RAW_TEXT_ELEMENTS: &[&str] = &["textarea", "title"];
+
+/// Elements in which whitespace-only text is significant.
+const WHITESPACE_PRESERVING_ELEMENTS: &[&str] = &["pre", "textarea"];
+
+fn is_one_of(name: &str, list: &[&str]) -> bool {
+    list.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// Returns `true` if `name` is a void element, matching case-insensitively.
+pub(crate) fn is_void_element(name: &str) -> bool {
+    is_one_of(name, VOID_ELEMENTS)
+}
+
+fn error_at(position: usize, message: impl std::fmt::Display) -> ParseError {
+    ParseError {
+        message: format!("{message} at byte {position}"),
+    }
+}
+
+/// A token of plain HTML, as produced by [`tokenize`].
+#[derive(Debug, Clone)]
+pub enum Token {
+    /// A start tag, such as `<a href="/">` or `<br/>`.
+    StartTag {
+        /// The tag name, as written.
+        name: String,
+        /// The attributes, with character references in their values decoded.
+        attributes: Vec<AstAttribute>,
+        /// Whether the tag ends with `/>`.
+        self_closing: bool,
+    },
+    /// An end tag, such as `</a>`.
+    EndTag {
+        /// The tag name, as written.
+        name: String,
+    },
+    /// Text, with character references decoded. This includes the content of `<textarea>`
+    /// and `<title>`.
+    Text(String),
+    /// The verbatim content of a `<script>` or `<style>` element.
+    RawText(String),
+    /// A comment, with the content between `<!--` and `-->`.
+    Comment(String),
+    /// A doctype declaration, with the content after `<!DOCTYPE` (e.g. `html`).
+    Doctype(String),
+}
+
+struct Tokenizer<'a> {
+    input: &'a str,
+    position: usize,
+    /// Set after a raw text or escapable raw text start tag, until its end tag is found.
+    raw_text_element: Option<String>,
+}
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            position: 0,
+            raw_text_element: None,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.input[start..self.position]
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(error_at(
+                self.position - c.len_utf8(),
+                format!("Expected `{expected}`, found `{c}`"),
+            )),
+            None => Err(error_at(
+                self.position,
+                format!("Expected `{expected}`, found end of input"),
+            )),
+        }
+    }
+
+    /// Returns the next token and the byte offset at which it starts.
+    fn next_token(&mut self) -> Result<Option<(usize, Token)>, ParseError> {
+        let start = self.position;
+        if self.position >= self.input.len() {
+            return Ok(None);
+        }
+
+        if let Some(name) = self.raw_text_element.take() {
+            let end = find_end_tag(self.rest(), &name)
+                .map(|offset| self.position + offset)
+                .unwrap_or(self.input.len());
+            let text = self.input[self.position..end].to_string();
+            self.position = end;
+            let token = if is_one_of(&name, RAW_TEXT_ELEMENTS) {
+                Token::RawText(text)
+            } else {
+                Token::Text(decode_entities(&text))
+            };
+            return Ok(Some((start, token)));
+        }
+
+        let rest = self.rest();
+        let token = if let Some(comment) = rest.strip_prefix("<!--") {
+            let Some(end) = comment.find("-->") else {
+                return Err(error_at(start, "Unclosed comment"));
+            };
+            self.position += "<!--".len() + end + "-->".len();
+            Token::Comment(comment[..end].to_string())
+        } else if let Some(declaration) = rest.strip_prefix("<!") {
+            let Some(end) = declaration.find('>') else {
+                return Err(error_at(start, "Unclosed declaration"));
+            };
+            let declaration = &declaration[..end];
+            self.position += "<!".len() + end + ">".len();
+            match declaration.get(.."doctype".len()) {
+                Some(keyword) if keyword.eq_ignore_ascii_case("doctype") => {
+                    Token::Doctype(declaration["doctype".len()..].trim().to_string())
+                }
+                _ => return Err(error_at(start, "Unsupported declaration")),
+            }
+        } else if rest.starts_with("</") {
+            self.position += "</".len();
+            let name = self.take_while(is_tag_name_char).to_string();
+            if name.is_empty() {
+                return Err(error_at(self.position, "Expected closing tag name"));
+            }
+            self.skip_whitespace();
+            self.expect('>')?;
+            Token::EndTag { name }
+        } else if rest.starts_with('<') {
+            self.bump();
+            if !self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                return Err(error_at(start, "Expected tag name after `<`"));
+            }
+            let name = self.take_while(is_tag_name_char).to_string();
+            let (attributes, self_closing) = self.attributes()?;
+            if !self_closing
+                && (is_one_of(&name, RAW_TEXT_ELEMENTS)
+                    || is_one_of(&name, ESCAPABLE_RAW_TEXT_ELEMENTS))
+            {
+                self.raw_text_element = Some(name.clone());
+            }
+            Token::StartTag {
+                name,
+                attributes,
+                self_closing,
+            }
+        } else {
+            Token::Text(decode_entities(self.take_while(|c| c != '<')))
+        };
+
+        Ok(Some((start, token)))
+    }
+
+    /// Parses the attributes of a start tag, up to and including its closing `>`.
+    ///
+    /// Returns the attributes and whether the tag was self-closing (`/>`).
+    fn attributes(&mut self) -> Result<(Vec<AstAttribute>, bool), ParseError> {
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err(error_at(self.position, "Unclosed start tag")),
+                Some('>') => {
+                    self.bump();
+                    return Ok((attributes, false));
+                }
+                Some('/') => {
+                    self.bump();
+                    self.expect('>')?;
+                    return Ok((attributes, true));
+                }
+                Some(_) => {}
+            }
+
+            let name_start = self.position;
+            let name = self
+                .take_while(|c| !c.is_whitespace() && !matches!(c, '=' | '>' | '/' | '"' | '\''))
+                .to_string();
+            if name.is_empty() {
+                return Err(error_at(name_start, "Expected attribute name"));
+            }
+
+            self.skip_whitespace();
+            if self.peek() != Some('=') {
+                attributes.push(AstAttribute::Named { name, value: None });
+                continue;
+            }
+            self.bump();
+            self.skip_whitespace();
+
+            let value = match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    let quote_start = self.position;
+                    self.bump();
+                    let value = self.take_while(|c| c != quote).to_string();
+                    if self.bump().is_none() {
+                        return Err(error_at(quote_start, "Unclosed attribute value"));
+                    }
+                    value
+                }
+                _ => {
+                    let value = self
+                        .take_while(|c| !c.is_whitespace() && c != '>')
+                        .to_string();
+                    if value.is_empty() {
+                        return Err(error_at(self.position, "Expected attribute value"));
+                    }
+                    value
+                }
+            };
+            attributes.push(AstAttribute::Named {
+                name,
+                value: Some(AttributeValue::LiteralString(decode_entities(&value))),
+            });
+        }
+    }
+}
+
+/// Decodes named and numeric character references. Unknown references and `&`s that do not
+/// start a reference are left verbatim.
+fn decode_entities(text: &str) -> String {
+    html_escape::decode_html_entities(text).into_owned()
+}
+
+fn is_tag_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+/// Finds the byte offset of the end tag for `name` in `input`, matching case-insensitively.
+fn find_end_tag(input: &str, name: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(index) = input[offset..].find("</") {
+        let candidate = offset + index;
+        let after = &input[candidate + "</".len()..];
+        if after
+            .get(..name.len())
+            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            && !after[name.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_tag_name_char)
+        {
+            return Some(candidate);
+        }
+        offset = candidate + "</".len();
+    }
+    None
+}
+
+/// An element that has been opened but not yet closed.
+struct OpenElement {
+    name: String,
+    attributes: Vec<AstAttribute>,
+    children: Vec<AstNode>,
+    child_spans: Vec<AstNodeSpanned>,
+    start_tag: Span,
+}
+
+/// Builds the tree from a stream of tokens.
+///
+/// If `errors` is set, errors are collected there and parsing continues; otherwise,
+/// the first error aborts parsing.
+struct TreeBuilder<'e> {
+    root: Vec<AstNode>,
+    root_spans: Vec<AstNodeSpanned>,
+    stack: Vec<OpenElement>,
+    errors: Option<&'e mut Vec<ParseError>>,
+}
+impl TreeBuilder<'_> {
+    fn recover(&mut self, error: ParseError) -> Result<(), ParseError> {
+        match &mut self.errors {
+            Some(errors) => {
+                errors.push(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// The children of the innermost open element, and their spans.
+    fn children(&mut self) -> (&mut Vec<AstNode>, &mut Vec<AstNodeSpanned>) {
+        match self.stack.last_mut() {
+            Some(parent) => (&mut parent.children, &mut parent.child_spans),
+            None => (&mut self.root, &mut self.root_spans),
+        }
+    }
+
+    fn push_child(&mut self, node: AstNode, span: AstNodeSpanned) {
+        let (children, child_spans) = self.children();
+        children.push(node);
+        child_spans.push(span);
+    }
+
+    fn push_text(&mut self, text: String, span: Span) {
+        let (children, child_spans) = self.children();
+        match (children.last_mut(), child_spans.last_mut()) {
+            (Some(AstNode::Text(previous)), Some(previous_span)) => {
+                previous.push_str(&text);
+                previous_span.span.end = span.end;
+            }
+            _ => {
+                children.push(AstNode::Text(text));
+                child_spans.push(AstNodeSpanned::leaf(span));
+            }
+        }
+    }
+
+    /// Closes the innermost open element, adding it to its parent.
+    ///
+    /// `end` is the end of its closing tag; if it was closed implicitly, the element ends
+    /// with its last child instead.
+    fn close_innermost(&mut self, end: Option<usize>) {
+        let Some(open) = self.stack.pop() else {
+            return;
+        };
+        let end = end.unwrap_or_else(|| {
+            open.child_spans
+                .last()
+                .map_or(open.start_tag.end, |child| child.span.end)
+        });
+        let element = AstNode::Element {
+            name: open.name,
+            attributes: open.attributes,
+            children: open.children,
+            void: false,
+        };
+        let span = AstNodeSpanned {
+            span: Span {
+                start: open.start_tag.start,
+                end,
+            },
+            children: open.child_spans,
+        };
+        self.push_child(element, span);
+    }
+
+    fn end_tag(&mut self, span: Span, name: String) -> Result<(), ParseError> {
+        let position = span.start;
+        let matching = self
+            .stack
+            .iter()
+            .rposition(|open| open.name.eq_ignore_ascii_case(&name));
+        let Some(innermost) = self.stack.last() else {
+            // Stray closing tags are ignored
+            return self.recover(error_at(
+                position,
+                format!("Unexpected closing tag `</{name}>`"),
+            ));
+        };
+
+        if matching != Some(self.stack.len() - 1) {
+            let error = error_at(
+                position,
+                format!(
+                    "Mismatched closing tag `</{name}>` for `<{}>`",
+                    innermost.name
+                ),
+            );
+            self.recover(error)?;
+        }
+        // If the tag matches an outer element, the elements within it are closed
+        // implicitly; if it matches nothing, it is ignored
+        if let Some(index) = matching {
+            while self.stack.len() > index + 1 {
+                self.close_innermost(None);
+            }
+            self.close_innermost(Some(span.end));
+        }
+        Ok(())
+    }
+
+    fn token(&mut self, span: Span, token: Token) -> Result<(), ParseError> {
+        match token {
+            Token::StartTag {
+                name,
+                attributes,
+                self_closing,
+            } => {
+                if self_closing || is_void_element(&name) {
+                    let element = AstNode::Element {
+                        name,
+                        attributes,
+                        children: vec![],
+                        void: true,
+                    };
+                    self.push_child(element, AstNodeSpanned::leaf(span));
+                } else {
+                    self.stack.push(OpenElement {
+                        name,
+                        attributes,
+                        children: vec![],
+                        child_spans: vec![],
+                        start_tag: span,
+                    });
+                }
+            }
+            Token::EndTag { name } => self.end_tag(span, name)?,
+            Token::Text(text) => {
+                let preserve_whitespace = self
+                    .stack
+                    .iter()
+                    .any(|e| is_one_of(&e.name, WHITESPACE_PRESERVING_ELEMENTS));
+                if preserve_whitespace || !text.trim().is_empty() {
+                    self.push_text(text, span);
+                }
+            }
+            Token::RawText(html) => self.push_child(AstNode::Raw(html), AstNodeSpanned::leaf(span)),
+            Token::Comment(_) => {}
+            Token::Doctype(content) => {
+                let element = AstNode::Element {
+                    name: "!DOCTYPE".to_string(),
+                    attributes: content
+                        .split_whitespace()
+                        .map(|word| AstAttribute::Named {
+                            name: word.to_string(),
+                            value: None,
+                        })
+                        .collect(),
+                    children: vec![],
+                    void: true,
+                };
+                self.push_child(element, AstNodeSpanned::leaf(span));
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes any open elements and returns the root node. `len` is the length of the
+    /// input, which a top-level fragment spans.
+    fn finish(mut self, len: usize) -> Result<(AstNode, AstNodeSpanned), ParseError> {
+        while let Some(open) = self.stack.last() {
+            let error = error_at(
+                open.start_tag.start,
+                format!("Unclosed tag `<{}>`", open.name),
+            );
+            self.recover(error)?;
+            self.close_innermost(None);
+        }
+
+        if self.root.len() == 1 {
+            Ok((self.root.pop().unwrap(), self.root_spans.pop().unwrap()))
+        } else {
+            let span = AstNodeSpanned {
+                span: Span { start: 0, end: len },
+                children: self.root_spans,
+            };
+            Ok((AstNode::Fragment(self.root), span))
+        }
+    }
+}
+
+fn parse(
+    html: &str,
+    errors: Option<&mut Vec<ParseError>>,
+) -> Result<(AstNode, AstNodeSpanned), ParseError> {
+    let mut tokenizer = Tokenizer::new(html);
+    let mut builder = TreeBuilder {
+        root: Vec::new(),
+        root_spans: Vec::new(),
+        stack: Vec::new(),
+        errors,
+    };
+
+    loop {
+        let start = tokenizer.position;
+        match tokenizer.next_token() {
+            Ok(Some((start, token))) => {
+                let end = tokenizer.position;
+                builder.token(Span { start, end }, token)?
+            }
+            Ok(None) => break,
+            Err(error) => {
+                builder.recover(error)?;
+                // Every token that can fail starts with `<`, which is treated as text
+                tokenizer.position = start + '<'.len_utf8();
+                let span = Span {
+                    start,
+                    end: tokenizer.position,
+                };
+                builder.token(span, Token::Text("<".to_string()))?;
+            }
+        }
+    }
+
+    builder.finish(html.len())
+}
+
+/// Split a plain HTML string into [`Token`]s, each with the [`Span`] of input it covers.
+///
+/// This is the tokenizer used by [`parse_plain_html`], exposed for tooling that needs the
+/// source structure, such as linters and formatters. Unlike the parser, it does not check
+/// that tags are balanced, and keeps comments and whitespace-only text.
+///
+/// Iteration stops after the first error.
+pub fn tokenize(input: &str) -> impl Iterator<Item = Result<(Token, Span), ParseError>> + '_ {
+    let mut tokenizer = Tokenizer::new(input);
+    let mut failed = false;
+    std::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        match tokenizer.next_token() {
+            Ok(Some((start, token))) => Some(Ok((
+                token,
+                Span {
+                    start,
+                    end: tokenizer.position,
+                },
+            ))),
+            Ok(None) => None,
+            Err(error) => {
+                failed = true;
+                Some(Err(error))
+            }
+        }
+    })
+}
+
+/// Parse a plain HTML string into an AST.
+///
+/// Unlike [`crate::parse_html`], text content is not quoted (`<p>Hello</p>`), and
+/// interpolation is not supported. A single top-level node is returned as-is; otherwise,
+/// the top-level nodes are wrapped in an [`AstNode::Fragment`].
+///
+/// The following are handled specially:
+/// - Void elements (e.g. `<br>`) do not need to be self-closed.
+/// - The contents of `<script>` and `<style>` are returned as [`AstNode::Raw`].
+/// - The contents of `<textarea>` and `<title>` are returned as text, without parsing tags.
+/// - Character references in text and attribute values are decoded, so that rendering
+///   re-encodes them exactly once.
+/// - Comments are discarded.
+/// - Whitespace-only text is discarded, except within `<pre>` and `<textarea>`.
+///
+/// Parsing fails on the first error; see [`parse_plain_html_lenient`] for untrusted input.
+pub fn parse_plain_html(html: &str) -> Result<AstNode, ParseError> {
+    parse(html, None).map(|(node, _)| node)
+}
+
+/// Parse a plain HTML string into an AST as with [`parse_plain_html`], also returning the
+/// [`Span`] of input that each node covers.
+///
+/// The returned [`AstNodeSpanned`] tree mirrors the [`AstNode`] tree. An element spans from
+/// the start of its start tag to the end of its end tag; a top-level fragment spans the
+/// whole input.
+pub fn parse_plain_html_spanned(html: &str) -> Result<(AstNode, AstNodeSpanned), ParseError> {
+    parse(html, None)
+}
+
+/// Parse a plain HTML string into an AST, recovering from errors instead of failing.
+///
+/// This accepts the same input as [`parse_plain_html`], and returns the parsed tree along
+/// with every error that was recovered from:
+/// - Tags that are still open at the end of the input are closed.
+/// - A closing tag that matches an outer open element closes every element within it.
+/// - A closing tag that matches no open element is ignored.
+/// - A `<` that does not start a valid tag, comment, or declaration is treated as text.
+pub fn parse_plain_html_lenient(html: &str) -> (AstNode, Vec<ParseError>) {
+    let mut errors = Vec::new();
+    let (node, _) = parse(html, Some(&mut errors)).expect("lenient parsing never fails");
+    (node, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(node: &AstNode) -> &str {
+        match node {
+            AstNode::Text(text) => text,
+            _ => panic!("Expected text node, got {node:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_unquoted_text() {
+        let result = parse_plain_html("<p>Hello world</p>").unwrap();
+
+        match result {
+            AstNode::Element { name, children, .. } => {
+                assert_eq!(name, "p");
+                assert_eq!(children.len(), 1);
+                assert_eq!(text_of(&children[0]), "Hello world");
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_text_with_entity() {
+        let result = parse_plain_html("<p>a &amp; b</p>").unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => {
+                assert_eq!(text_of(&children[0]), "a & b");
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_decodes_named_and_numeric_entities() {
+        let result =
+            parse_plain_html(r#"<p title="&quot;x&quot;">&lt;&#39;&#x41;&copy;&gt;</p>"#).unwrap();
+
+        match result {
+            AstNode::Element {
+                attributes,
+                children,
+                ..
+            } => {
+                match &attributes[0] {
+                    AstAttribute::Named {
+                        value: Some(value), ..
+                    } => assert_eq!(value.to_string_value(), "\"x\""),
+                    _ => panic!("Expected valued attribute"),
+                }
+                assert_eq!(text_of(&children[0]), "<'A©>");
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_leaves_unknown_entities_verbatim() {
+        let result = parse_plain_html("<p>&bogus; & AT&T</p>").unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => {
+                assert_eq!(text_of(&children[0]), "&bogus; & AT&T");
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let result =
+            parse_plain_html(r#"<a href="/x" class='link' data-id=3 download>"Go"</a>"#).unwrap();
+
+        match result {
+            AstNode::Element {
+                attributes,
+                children,
+                ..
+            } => {
+                assert_eq!(attributes.len(), 4);
+                let values: Vec<_> = attributes
+                    .iter()
+                    .map(|a| match a {
+                        AstAttribute::Named { name, value } => {
+                            (name.as_str(), value.as_ref().map(|v| v.to_string_value()))
+                        }
+                        _ => panic!("Expected named attribute"),
+                    })
+                    .collect();
+                assert_eq!(
+                    values,
+                    vec![
+                        ("href", Some("/x".to_string())),
+                        ("class", Some("link".to_string())),
+                        ("data-id", Some("3".to_string())),
+                        ("download", None),
+                    ]
+                );
+                // Quotes in text content are kept verbatim
+                assert_eq!(text_of(&children[0]), "\"Go\"");
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_void_elements_without_slash() {
+        let result = parse_plain_html("<p>a<br>b</p>").unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => {
+                assert_eq!(children.len(), 3);
+                assert!(matches!(
+                    &children[1],
+                    AstNode::Element { name, void: true, .. } if name == "br"
+                ));
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_discards_whitespace_and_comments() {
+        let result = parse_plain_html("<ul>\n  <!-- items -->\n  <li>One</li>\n</ul>").unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => {
+                assert_eq!(children.len(), 1);
+                assert_eq!(children[0].element_name(), Some("li"));
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_preserves_whitespace_in_pre() {
+        let result = parse_plain_html("<pre>  </pre>").unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => {
+                assert_eq!(text_of(&children[0]), "  ");
+            }
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_script_as_raw() {
+        let result = parse_plain_html("<script>if (a < b) { x(); }</script>").unwrap();
+
+        match result {
+            AstNode::Element { children, .. } => match &children[0] {
+                AstNode::Raw(html) => assert_eq!(html, "if (a < b) { x(); }"),
+                _ => panic!("Expected raw node"),
+            },
+            _ => panic!("Expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_multiple_top_level_nodes_and_doctype() {
+        let result = parse_plain_html("<!DOCTYPE html><html></html>").unwrap();
+
+        match result {
+            AstNode::Fragment(children) => {
+                assert_eq!(children.len(), 2);
+                assert_eq!(children[0].element_name(), Some("!DOCTYPE"));
+                assert_eq!(children[1].element_name(), Some("html"));
+            }
+            _ => panic!("Expected fragment"),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_plain_html("<div>").is_err());
+        assert!(parse_plain_html("<div></span>").is_err());
+        assert!(parse_plain_html("</div>").is_err());
+        assert!(parse_plain_html("<div class=\"x></div>").is_err());
+    }
+
+    #[test]
+    fn test_parse_lenient_closes_unclosed_tags() {
+        let (result, errors) = parse_plain_html_lenient("<div><p>Hello");
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "Unclosed tag `<p>` at byte 5");
+        assert_eq!(errors[1].message, "Unclosed tag `<div>` at byte 0");
+        let AstNode::Element { name, children, .. } = result else {
+            panic!("Expected element");
+        };
+        assert_eq!(name, "div");
+        let AstNode::Element { name, children, .. } = &children[0] else {
+            panic!("Expected element");
+        };
+        assert_eq!(name, "p");
+        assert_eq!(text_of(&children[0]), "Hello");
+    }
+
+    #[test]
+    fn test_parse_lenient_mismatched_and_stray_tags() {
+        let (result, errors) = parse_plain_html_lenient("<div><b>a < b</div></span><i>c</i>");
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].message.starts_with("Expected tag name after `<`"));
+        assert!(errors[1]
+            .message
+            .starts_with("Mismatched closing tag `</div>` for `<b>`"));
+        assert!(errors[2]
+            .message
+            .starts_with("Unexpected closing tag `</span>`"));
+
+        let AstNode::Fragment(nodes) = result else {
+            panic!("Expected fragment");
+        };
+        assert_eq!(nodes.len(), 2);
+        let AstNode::Element { name, children, .. } = &nodes[0] else {
+            panic!("Expected element");
+        };
+        assert_eq!(name, "div");
+        let AstNode::Element { name, children, .. } = &children[0] else {
+            panic!("Expected element");
+        };
+        assert_eq!(name, "b");
+        assert_eq!(text_of(&children[0]), "a < b");
+        assert_eq!(nodes[1].element_name(), Some("i"));
+    }
+
+    #[test]
+    fn test_parse_lenient_matches_strict_on_valid_input() {
+        let html = "<ul><li>One</li><li>Two<br></li></ul>";
+        let (result, errors) = parse_plain_html_lenient(html);
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            format!("{result:?}"),
+            format!("{:?}", parse_plain_html(html).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_spans() {
+        let input = "<!DOCTYPE html><p class=\"a\">Hi &amp; bye<!-- note --><br/></p>";
+        let tokens: Vec<_> = tokenize(input).collect::<Result<_, _>>().unwrap();
+        let spans: Vec<_> = tokens
+            .iter()
+            .map(|(_, span)| &input[span.start..span.end])
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                "<!DOCTYPE html>",
+                "<p class=\"a\">",
+                "Hi &amp; bye",
+                "<!-- note -->",
+                "<br/>",
+                "</p>"
+            ]
+        );
+
+        assert!(matches!(&tokens[0].0, Token::Doctype(content) if content == "html"));
+        assert!(matches!(
+            &tokens[1].0,
+            Token::StartTag { name, attributes, self_closing: false }
+                if name == "p" && attributes.len() == 1
+        ));
+        assert!(matches!(&tokens[2].0, Token::Text(text) if text == "Hi & bye"));
+        assert!(matches!(&tokens[3].0, Token::Comment(comment) if comment == " note "));
+        assert!(matches!(
+            &tokens[4].0,
+            Token::StartTag { name, self_closing: true, .. } if name == "br"
+        ));
+        assert!(matches!(&tokens[5].0, Token::EndTag { name } if name == "p"));
+        assert_eq!(tokens[0].1, Span { start: 0, end: 15 });
+        assert_eq!(tokens[5].1, Span { start: 58, end: 62 });
+        assert_eq!(tokens[5].1.end, input.len());
+    }
+
+    #[test]
+    fn test_tokenize_stops_after_error() {
+        let results: Vec<_> = tokenize("<p>ok</p><!-- open").collect();
+        assert_eq!(results.len(), 4);
+        assert!(results[..3].iter().all(Result::is_ok));
+        assert!(results[3].is_err());
+
+        let script: Vec<_> = tokenize("<script>if (a < b) {}</script>")
+            .map(|result| result.unwrap().0)
+            .collect();
+        assert!(matches!(&script[1], Token::RawText(text) if text == "if (a < b) {}"));
+    }
+
+    #[test]
+    fn test_parse_spans_of_nested_elements() {
+        let html = "<div id=\"a\">\n  <p>One <b>two</b></p>\n  <br>\n</div>";
+        let (node, spans) = parse_plain_html_spanned(html).unwrap();
+        let source = |span: &AstNodeSpanned| &html[span.span.start..span.span.end];
+
+        assert_eq!(source(&spans), html);
+        let AstNode::Element { children, .. } = &node else {
+            panic!("Expected element");
+        };
+        assert_eq!(children.len(), spans.children.len());
+
+        let p = &spans.children[0];
+        assert_eq!(source(p), "<p>One <b>two</b></p>");
+        assert_eq!(p.span, Span { start: 15, end: 36 });
+        assert_eq!(source(&p.children[0]), "One ");
+        assert_eq!(source(&p.children[1]), "<b>two</b>");
+        assert_eq!(source(&p.children[1].children[0]), "two");
+        assert_eq!(source(&spans.children[1]), "<br>");
+
+        // Implicitly closed elements end with their last child
+        let mut errors = vec![];
+        let (_, spans) = parse("<div><p>a</div>", Some(&mut errors)).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(spans.children[0].span, Span { start: 5, end: 9 });
+        assert_eq!(spans.span, Span { start: 0, end: 15 });
+    }
+}
@@ -3,7 +3,8 @@
 use std::fmt;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 /// Custom error type for paxhtml_tailwind operations
 ///
@@ -30,6 +31,8 @@ pub enum TailwindError {
     DownloadFailed { error: std::io::Error },
     /// Failed to execute tailwind command
     ExecutionFailed(String),
+    /// The process did not finish within the given timeout, and was killed
+    Timeout { timeout: Duration },
 }
 impl fmt::Display for TailwindError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -50,6 +53,9 @@ impl fmt::Display for TailwindError {
             TailwindError::UnsupportedPlatform => write!(f, "Unsupported platform"),
             TailwindError::DownloadFailed { error } => write!(f, "Download failed: {error}"),
             TailwindError::ExecutionFailed(msg) => write!(f, "Execution failed: {msg}"),
+            TailwindError::Timeout { timeout } => {
+                write!(f, "Process did not finish within {timeout:?}")
+            }
         }
     }
 }
@@ -204,17 +210,153 @@ impl Tailwind {
 
     /// Run the Tailwind executable with the given arguments.
     pub fn run_command_to_completion(&self, args: &[&str]) -> Result<String> {
-        let output = self.create_command_with_args(args)?.output()?;
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(self.run_command_capturing(args)?.stdout)
+    }
+
+    /// Run the Tailwind executable with the given arguments, returning both its stdout and
+    /// stderr.
+    ///
+    /// Tailwind reports warnings, such as unknown utilities, on stderr even when it succeeds.
+    pub fn run_command_capturing(&self, args: &[&str]) -> Result<TailwindOutput> {
+        self.run_command_with_timeout(args, None)
+    }
+
+    /// Run the Tailwind executable with the given arguments, as with
+    /// [`Self::run_command_capturing`].
+    ///
+    /// If `timeout` is given and the process has not finished by then, it is killed and
+    /// [`TailwindError::Timeout`] is returned. Only the process itself is killed: if it
+    /// started processes of its own that keep its output open, the threads reading that
+    /// output are left to finish in the background.
+    pub fn run_command_with_timeout(
+        &self,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<TailwindOutput> {
+        let mut child = self
+            .create_command_with_args(args)?
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Read both streams on their own threads, so that the process can't block on a
+        // full pipe while we wait for it
+        let read_to_end = |mut stream: Box<dyn io::Read + Send>| {
+            std::thread::spawn(move || {
+                let mut buffer = vec![];
+                stream.read_to_end(&mut buffer).map(|_| buffer)
+            })
+        };
+        let stdout = read_to_end(Box::new(child.stdout.take().unwrap()));
+        let stderr = read_to_end(Box::new(child.stderr.take().unwrap()));
 
-        if !output.status.success() {
+        let status = match timeout {
+            None => child.wait()?,
+            Some(timeout) => {
+                let deadline = Instant::now() + timeout;
+                loop {
+                    if let Some(status) = child.try_wait()? {
+                        break status;
+                    }
+                    if Instant::now() >= deadline {
+                        child.kill()?;
+                        child.wait()?;
+                        // The reader threads are not joined, as a grandchild holding the
+                        // pipes open would block them indefinitely
+                        return Err(TailwindError::Timeout { timeout });
+                    }
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+        };
+
+        let join = |handle: std::thread::JoinHandle<io::Result<Vec<u8>>>| {
+            handle
+                .join()
+                .unwrap_or_else(|_| Err(io::Error::other("output reader panicked")))
+                .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+        };
+        let stdout = join(stdout)?;
+        let stderr = join(stderr)?;
+
+        if !status.success() {
             return Err(TailwindError::ProcessExecution {
-                stdout: stdout.to_string(),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-                exit_code: output.status.code(),
+                stdout,
+                stderr,
+                exit_code: status.code(),
             });
         }
 
-        Ok(stdout.to_string())
+        Ok(TailwindOutput { stdout, stderr })
+    }
+}
+
+/// The output of a successful Tailwind run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TailwindOutput {
+    /// The standard output, such as the generated CSS.
+    pub stdout: String,
+    /// The standard error, which may contain warnings.
+    pub stderr: String,
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    // Writes an executable shell script standing in for Tailwind to a temporary directory
+    fn fake_tailwind(name: &str, script: &str) -> (PathBuf, Tailwind) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir =
+            std::env::temp_dir().join(format!("paxhtml-tailwind-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tailwind");
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        (dir, Tailwind::Local(path))
+    }
+
+    #[test]
+    fn captures_stderr_on_success() {
+        let (dir, tailwind) = fake_tailwind(
+            "stderr",
+            "echo \"css $1\"\necho 'warning: unknown utility' >&2",
+        );
+        let output = tailwind.run_command_capturing(&["--minify"]).unwrap();
+        assert_eq!(output.stdout, "css --minify\n");
+        assert_eq!(output.stderr, "warning: unknown utility\n");
+        assert_eq!(
+            tailwind.run_command_to_completion(&["--minify"]).unwrap(),
+            "css --minify\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn kills_process_after_timeout() {
+        let (dir, tailwind) = fake_tailwind("timeout", "exec sleep 10");
+
+        let start = Instant::now();
+        let result = tailwind.run_command_with_timeout(&[], Some(Duration::from_millis(100)));
+        assert!(matches!(result, Err(TailwindError::Timeout { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn timeout_does_not_wait_for_grandchildren() {
+        // The background `sleep` survives the shell being killed, and keeps its output open
+        let (dir, tailwind) = fake_tailwind("grandchild", "sleep 10 & wait");
+
+        let start = Instant::now();
+        let result = tailwind.run_command_with_timeout(&[], Some(Duration::from_millis(100)));
+        assert!(matches!(result, Err(TailwindError::Timeout { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }